@@ -1,4 +1,5 @@
 use ash_sdl_vulkan_tutorial::build_tasks::{self, Config};
+use ash_sdl_vulkan_tutorial::shaders::SCALAR_BLOCK_LAYOUT;
 use ash_sdl_vulkan_tutorial::util::manifest_path;
 
 pub fn main() {
@@ -16,6 +17,7 @@ pub fn main() {
         rust_source_dir: manifest_path(["src"]),
         shaders_source_dir: manifest_path(["shaders", "source"]),
         compiled_shaders_dir: manifest_path(["shaders", "compiled"]),
+        scalar_block_layout: SCALAR_BLOCK_LAYOUT,
     };
 
     build_tasks::write_precompiled_shaders(config).unwrap();
@@ -9,30 +9,25 @@ use ash::util::read_spv;
 use ash::vk;
 use serde::Serialize;
 
-use crate::renderer::gpu_write::GPUWrite;
 use crate::renderer::vertex_description::VertexDescription;
 use crate::renderer::*;
 use crate::shaders::atlas::{PrecompiledShader, PrecompiledShaders, ShaderAtlasEntry};
-use crate::shaders::json::{ReflectedPipelineLayout, ReflectionJson};
-
-#[derive(Debug, Clone, Serialize)]
-#[repr(C, align(16))]
-pub struct MVPMatrices {
-    pub model: glam::Mat4,
-    pub view: glam::Mat4,
-    pub proj: glam::Mat4,
-}
+use crate::shaders::json::{ReflectedPipelineLayout, ReflectionJson, ShaderVariant};
 
-impl GPUWrite for MVPMatrices {}
+pub use crate::generated::shared::MVPMatrices;
 
-#[derive(Debug, Clone, Serialize)]
-#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, Serialize, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
 pub struct Vertex {
     pub position: glam::Vec3,
     pub color: glam::Vec3,
 }
 
-impl GPUWrite for Vertex {}
+const _: () = assert!(std::mem::offset_of!(Vertex, position) == 0);
+const _: () = assert!(std::mem::offset_of!(Vertex, color) == 12);
+const _: () = assert!(std::mem::size_of::<Vertex>() == 24);
+
+impl crate::renderer::gpu_write::VertexData for Vertex {}
 
 pub struct Resources<'a> {
     pub vertices: Vec<Vertex>,
@@ -68,6 +63,7 @@ impl VertexDescription for Vertex {
 
 pub struct Shader {
     pub reflection_json: ReflectionJson,
+    pub specialization_constants: SpecializationConstants,
 }
 
 impl Shader {
@@ -79,27 +75,31 @@ impl Shader {
 
         let reflection_json: ReflectionJson = serde_json::from_str(json_str).unwrap();
 
-        Self { reflection_json }
+        Self {
+            reflection_json,
+            specialization_constants: SpecializationConstants::new(),
+        }
     }
 
-    pub fn pipeline_config(self, resources: Resources<'_>) -> PipelineConfig<'_, Vertex> {
-        // NOTE this must be in descriptor set layout order in the reflection json
-        #[rustfmt::skip]
-        let texture_handles = vec![
-        ];
-
-        // NOTE this must be in descriptor set layout order in the reflection json
+    pub fn pipeline_config(self, resources: Resources<'_>) -> PipelineConfig<Vertex> {
+        // NOTE this must match the descriptor set layouts in the reflection json
         #[rustfmt::skip]
-        let uniform_buffer_handles = vec![
-            RawUniformBufferHandle::from_typed(resources.mvp_buffer),
+        let descriptor_set_handles = vec![
+            // mvp
+            vec![
+                RawDescriptorHandle::UniformBuffer(RawUniformBufferHandle::from_typed(resources.mvp_buffer)),
+            ],
         ];
 
         PipelineConfig {
             shader: Box::new(self),
+            vertex_count: resources.vertices.len() as u32,
             vertices: resources.vertices,
             indices: resources.indices,
-            texture_handles,
-            uniform_buffer_handles,
+            instances: vec![],
+            instance_count: 1,
+            descriptor_set_handles,
+            push_constants: std::marker::PhantomData,
         }
     }
 
@@ -107,6 +107,8 @@ impl Shader {
         let entry_point = self
             .reflection_json
             .vertex_entry_point
+            .as_ref()
+            .unwrap()
             .entry_point_name
             .clone();
 
@@ -117,6 +119,8 @@ impl Shader {
         let entry_point = self
             .reflection_json
             .fragment_entry_point
+            .as_ref()
+            .unwrap()
             .entry_point_name
             .clone();
 
@@ -147,6 +151,10 @@ impl ShaderAtlasEntry for Shader {
         &self.reflection_json.source_file_name
     }
 
+    fn variant(&self) -> Option<&ShaderVariant> {
+        self.reflection_json.variant.as_ref()
+    }
+
     fn vertex_binding_descriptions(&self) -> Vec<vk::VertexInputBindingDescription> {
         Vertex::binding_descriptions()
     }
@@ -159,16 +167,20 @@ impl ShaderAtlasEntry for Shader {
         self.reflection_json.layout_bindings()
     }
 
+    fn specialization_constants(&self) -> SpecializationConstants {
+        self.specialization_constants.clone()
+    }
+
     fn precompiled_shaders(&self) -> PrecompiledShaders {
         let vert = PrecompiledShader {
             entry_point_name: self.vert_entry_point_name(),
             spv_bytes: self.vert_spv(),
         };
 
-        let frag = PrecompiledShader {
+        let frag = Some(PrecompiledShader {
             entry_point_name: self.frag_entry_point_name(),
             spv_bytes: self.frag_spv(),
-        };
+        });
 
         PrecompiledShaders { vert, frag }
     }
@@ -0,0 +1,20 @@
+// GENERATED FILE (do not edit directly)
+
+//! structs from imported slang modules, shared between shader modules
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Serialize, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C, align(16))]
+pub struct MVPMatrices {
+    pub model: glam::Mat4,
+    pub view: glam::Mat4,
+    pub proj: glam::Mat4,
+}
+
+const _: () = assert!(std::mem::offset_of!(MVPMatrices, model) == 0);
+const _: () = assert!(std::mem::offset_of!(MVPMatrices, view) == 64);
+const _: () = assert!(std::mem::offset_of!(MVPMatrices, proj) == 128);
+const _: () = assert!(std::mem::size_of::<MVPMatrices>() == 192);
+
+impl crate::renderer::gpu_write::UniformData for MVPMatrices {}
@@ -12,7 +12,7 @@ use sdl3::video::Window;
 use vertex_description::VertexDescription;
 
 use crate::shaders;
-use crate::shaders::atlas::{PrecompiledShader, ShaderAtlasEntry};
+use crate::shaders::atlas::{ComputeShaderAtlasEntry, PrecompiledShader, ShaderAtlasEntry};
 
 #[cfg(debug_assertions)]
 use crate::shader_watcher;
@@ -22,20 +22,38 @@ use log::*;
 pub mod debug;
 mod platform;
 
+mod device_features;
+use device_features::DeviceFeatures;
+
 pub mod gpu_write;
-use gpu_write::{GPUWrite, write_to_gpu_buffer};
+use gpu_write::{
+    GpuPtr, PushConstantData, StorageData, UniformData, VertexData, write_to_gpu_buffer,
+};
 
 pub mod vertex_description;
+pub mod vertex_format;
 
 pub mod texture;
 pub use texture::*;
 
+pub mod sampler;
+pub use sampler::*;
+
 pub mod uniform_buffer;
 pub use uniform_buffer::*;
 
+pub mod storage_buffer;
+pub use storage_buffer::*;
+
 pub mod pipeline;
 pub use pipeline::*;
 
+pub mod compute_pipeline;
+pub use compute_pipeline::*;
+
+pub mod specialization;
+pub use specialization::*;
+
 /// enables both the validation layer and debug utils logging
 const ENABLE_VALIDATION: bool = cfg!(debug_assertions);
 /// applies MSAA-like sampling within textures
@@ -43,6 +61,9 @@ const ENABLE_SAMPLE_SHADING: bool = false;
 
 const MAX_FRAMES_IN_FLIGHT: usize = 2;
 
+/// the descriptor count for unbounded texture arrays, and the max number of live textures
+pub const BINDLESS_TEXTURE_CAPACITY: u32 = 1024;
+
 pub struct Renderer {
     // fields that are created once
     aspect_ratio: f32,
@@ -66,6 +87,7 @@ pub struct Renderer {
     surface: vk::SurfaceKHR,
     physical_device: vk::PhysicalDevice,
     physical_device_properties: vk::PhysicalDeviceProperties,
+    device_features: DeviceFeatures,
     queue_family_indices: QueueFamilyIndices,
     device: ash::Device,
     graphics_queue: vk::Queue,
@@ -99,8 +121,13 @@ pub struct Renderer {
     current_frame: usize,
 
     pipelines: PipelineStorage,
+    compute_pipelines: ComputePipelineStorage,
     textures: TextureStorage,
+    /// written over a dropped texture's slot in bindless texture arrays
+    bindless_fallback_texture: Texture,
+    samplers: SamplerStorage,
     uniform_buffers: UniformBufferStorage,
+    storage_buffers: StorageBufferStorage,
 }
 
 impl Renderer {
@@ -165,9 +192,14 @@ impl Renderer {
 
         let surface = window.vulkan_create_surface(instance.handle())?;
 
-        let (physical_device, queue_family_indices, physical_device_properties) =
+        let (physical_device, queue_family_indices, physical_device_properties, device_features) =
             choose_physical_device(&instance, &surface_ext, surface)?;
-        let device = create_logical_device(&instance, physical_device, &queue_family_indices)?;
+        let device = create_logical_device(
+            &instance,
+            physical_device,
+            &queue_family_indices,
+            &device_features,
+        )?;
 
         let msaa_samples = get_max_usable_sample_count(physical_device_properties);
 
@@ -206,6 +238,19 @@ impl Renderer {
         let command_pool = create_command_pool(&device, &queue_family_indices)?;
         let command_buffers = create_command_buffers(&device, command_pool)?;
 
+        // a 1x1 transparent black texture
+        let bindless_fallback_texture = create_texture::<Texture2D>(
+            "bindless_fallback".to_string(),
+            &[&image::DynamicImage::new_rgba8(1, 1)],
+            &instance,
+            &device,
+            physical_device,
+            physical_device_properties,
+            &device_features,
+            command_pool,
+            graphics_queue,
+        )?;
+
         let (color_image, color_image_memory, color_image_view) = create_color_image(
             &instance,
             &device,
@@ -235,8 +280,11 @@ impl Renderer {
         )?;
 
         let pipelines = PipelineStorage::new();
+        let compute_pipelines = ComputePipelineStorage::new();
         let textures = TextureStorage::new();
+        let samplers = SamplerStorage::new();
         let uniform_buffers = UniformBufferStorage::new();
+        let storage_buffers = StorageBufferStorage::new();
 
         Ok(Self {
             aspect_ratio,
@@ -254,6 +302,7 @@ impl Renderer {
             surface,
             physical_device,
             physical_device_properties,
+            device_features,
             queue_family_indices,
             device,
             graphics_queue,
@@ -281,12 +330,16 @@ impl Renderer {
             current_frame: 0,
 
             pipelines,
+            compute_pipelines,
             textures,
+            bindless_fallback_texture,
+            samplers,
             uniform_buffers,
+            storage_buffers,
         })
     }
 
-    fn renderer_pipeline(&self, handle: &PipelineHandle) -> &RendererPipeline {
+    fn renderer_pipeline<P>(&self, handle: &PipelineHandle<P>) -> &RendererPipeline {
         self.pipelines.get(handle)
     }
 
@@ -295,28 +348,149 @@ impl Renderer {
         source_file_name: impl Into<String>,
         image: &image::DynamicImage,
     ) -> anyhow::Result<TextureHandle> {
-        let texture = create_texture(
-            source_file_name.into(),
-            image,
+        self.add_texture(source_file_name.into(), &[image])
+    }
+
+    /// creates a 1D texture from an image with a height of 1
+    pub fn create_texture_1d(
+        &mut self,
+        source_file_name: impl Into<String>,
+        image: &image::DynamicImage,
+    ) -> anyhow::Result<TextureHandle<Texture1D>> {
+        if image.height() != 1 {
+            anyhow::bail!(
+                "expected a 1D texture image height of 1: {}",
+                image.height()
+            );
+        }
+
+        self.add_texture(source_file_name.into(), &[image])
+    }
+
+    /// creates a 3D texture from same-sized depth slices, ie a color grading LUT
+    pub fn create_texture_3d(
+        &mut self,
+        source_file_name: impl Into<String>,
+        slices: &[image::DynamicImage],
+    ) -> anyhow::Result<TextureHandle<Texture3D>> {
+        let slices: Vec<_> = slices.iter().collect();
+
+        self.add_texture(source_file_name.into(), &slices)
+    }
+
+    /// creates a cube map from square faces, in +x, -x, +y, -y, +z, -z order
+    pub fn create_cube_texture(
+        &mut self,
+        source_file_name: impl Into<String>,
+        faces: &[image::DynamicImage; 6],
+    ) -> anyhow::Result<TextureHandle<TextureCube>> {
+        if faces[0].width() != faces[0].height() {
+            anyhow::bail!(
+                "expected square cube faces: {}x{}",
+                faces[0].width(),
+                faces[0].height()
+            );
+        }
+        let faces: Vec<_> = faces.iter().collect();
+
+        self.add_texture(source_file_name.into(), &faces)
+    }
+
+    /// creates a 2D texture array from same-sized layers
+    pub fn create_texture_array(
+        &mut self,
+        source_file_name: impl Into<String>,
+        layers: &[image::DynamicImage],
+    ) -> anyhow::Result<TextureHandle<Texture2DArray>> {
+        let layers: Vec<_> = layers.iter().collect();
+
+        self.add_texture(source_file_name.into(), &layers)
+    }
+
+    fn add_texture<S: TextureShape>(
+        &mut self,
+        source_file_name: String,
+        images: &[&image::DynamicImage],
+    ) -> anyhow::Result<TextureHandle<S>> {
+        let Some(first_image) = images.first() else {
+            anyhow::bail!("no images for texture: {source_file_name}");
+        };
+        let dimensions = (first_image.width(), first_image.height());
+        if images
+            .iter()
+            .any(|image| (image.width(), image.height()) != dimensions)
+        {
+            anyhow::bail!("mismatched image sizes for texture: {source_file_name}");
+        }
+        if self.textures.next_index() >= BINDLESS_TEXTURE_CAPACITY as usize {
+            anyhow::bail!("exceeded bindless texture capacity: {source_file_name}");
+        }
+
+        let texture = create_texture::<S>(
+            source_file_name,
+            images,
             &self.instance,
             &self.device,
             self.physical_device,
             self.physical_device_properties,
+            &self.device_features,
             self.command_pool,
             self.graphics_queue,
         )?;
 
         let handle = self.textures.add(texture);
 
+        let raw_handle = RawTextureHandle::from_typed(&handle);
+        let texture = self.textures.get_raw(&raw_handle);
+        if texture.is_bindless() {
+            self.write_bindless_texture(raw_handle.bindless_index(), texture);
+        }
+
         Ok(handle)
     }
 
-    pub fn drop_texture(&mut self, texture_handle: TextureHandle) {
+    /// writes a texture into existing pipelines' bindless texture arrays
+    fn write_bindless_texture(&self, index: usize, texture: &Texture) {
+        let pipeline_sets = self
+            .pipelines
+            .iter()
+            .map(|p| (&p.descriptor_sets, p.shader.layout_bindings()));
+        let compute_pipeline_sets = self
+            .compute_pipelines
+            .iter()
+            .map(|p| (&p.descriptor_sets, p.shader.layout_bindings()));
+
+        for (descriptor_sets, layout_bindings) in pipeline_sets.chain(compute_pipeline_sets) {
+            // descriptor sets are arranged by frame, then by layout
+            for (ds, &dst_set) in descriptor_sets.iter().enumerate() {
+                for description in &layout_bindings[ds % layout_bindings.len()] {
+                    if let LayoutDescription::BindlessTextures(bindless_description) = description {
+                        write_bindless_texture(
+                            &self.device,
+                            dst_set,
+                            bindless_description,
+                            index,
+                            texture,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn drop_texture<S>(&mut self, texture_handle: TextureHandle<S>) {
+        let index = RawTextureHandle::from_typed(&texture_handle).bindless_index();
         let texture = self.textures.take(texture_handle);
-        self.destroy_texture(texture);
+
+        // so that bindless arrays never hold a destroyed image view
+        if texture.is_bindless() {
+            self.write_bindless_texture(index, &self.bindless_fallback_texture);
+        }
+
+        self.destroy_texture(&texture);
     }
 
-    fn destroy_texture(&mut self, texture: Texture) {
+    fn destroy_texture(&self, texture: &Texture) {
         unsafe {
             self.device.destroy_sampler(texture.sampler, None);
             self.device.destroy_image_view(texture.image_view, None);
@@ -325,7 +499,35 @@ impl Renderer {
         }
     }
 
-    pub fn create_uniform_buffer<T: GPUWrite>(&mut self) -> anyhow::Result<UniformBufferHandle<T>> {
+    /// creates a standalone sampler, for use with separate SamplerState fields
+    pub fn create_sampler(
+        &mut self,
+        filter: vk::Filter,
+        address_mode: vk::SamplerAddressMode,
+    ) -> anyhow::Result<SamplerHandle> {
+        let sampler = create_sampler(
+            &self.device,
+            self.physical_device_properties,
+            &self.device_features,
+            filter,
+            address_mode,
+        )?;
+
+        let handle = self.samplers.add(sampler);
+
+        Ok(handle)
+    }
+
+    pub fn drop_sampler(&mut self, sampler_handle: SamplerHandle) {
+        let sampler = self.samplers.take(sampler_handle);
+        unsafe {
+            self.device.destroy_sampler(sampler, None);
+        }
+    }
+
+    pub fn create_uniform_buffer<T: UniformData>(
+        &mut self,
+    ) -> anyhow::Result<UniformBufferHandle<T>> {
         let buffer_size = std::mem::size_of::<T>() as u64;
 
         let mut buffers_per_frame = Vec::with_capacity(MAX_FRAMES_IN_FLIGHT);
@@ -372,10 +574,118 @@ impl Renderer {
         }
     }
 
-    pub fn create_pipeline<V: VertexDescription>(
+    pub fn create_storage_buffer<T: StorageData>(
         &mut self,
-        config: PipelineConfig<V>,
-    ) -> anyhow::Result<PipelineHandle> {
+        elements: &[T],
+    ) -> anyhow::Result<StorageBufferHandle<T>> {
+        let storage_buffer = create_storage_buffer(
+            &self.instance,
+            &self.device,
+            self.physical_device,
+            self.command_pool,
+            self.graphics_queue,
+            elements,
+            self.device_features.buffer_device_address,
+        )?;
+
+        let handle = self.storage_buffers.add(storage_buffer);
+
+        Ok(handle)
+    }
+
+    /// NOTE this is single-buffered; every frame in flight reads and writes the same buffer,
+    /// so shader access to it is ordered between frames rather than overlapping
+    pub fn create_mutable_storage_buffer<T: StorageData>(
+        &mut self,
+        elements: &[T],
+    ) -> anyhow::Result<MutableStorageBufferHandle<T>> {
+        let storage_buffer = create_mutable_storage_buffer(
+            &self.instance,
+            &self.device,
+            self.physical_device,
+            elements,
+            self.device_features.buffer_device_address,
+        )?;
+
+        let handle = self.storage_buffers.add_mutable(storage_buffer);
+
+        Ok(handle)
+    }
+
+    /// a pointer to the buffer's first element, for slang pointer fields;
+    /// panics without buffer device address support, which shaders with pointers also need
+    pub fn storage_buffer_address<T>(&self, storage_buffer: &StorageBufferHandle<T>) -> GpuPtr<T> {
+        let raw_handle = RawStorageBufferHandle::from_typed(storage_buffer);
+        let raw_storage_buffer = self.storage_buffers.get_raw(&raw_handle);
+
+        let device_address = raw_storage_buffer
+            .device_address
+            .expect("the device doesn't support buffer device addresses");
+
+        GpuPtr::from_address(device_address)
+    }
+
+    /// a pointer to the buffer's first element, for slang pointer fields;
+    /// panics without buffer device address support, which shaders with pointers also need
+    pub fn mutable_storage_buffer_address<T>(
+        &self,
+        storage_buffer: &MutableStorageBufferHandle<T>,
+    ) -> GpuPtr<T> {
+        let raw_handle = RawStorageBufferHandle::from_mutable(storage_buffer);
+        let raw_storage_buffer = self.storage_buffers.get_raw(&raw_handle);
+
+        let device_address = raw_storage_buffer
+            .device_address
+            .expect("the device doesn't support buffer device addresses");
+
+        GpuPtr::from_address(device_address)
+    }
+
+    /// waits for in-flight frames to finish,
+    /// then copies out the current contents of the buffer,
+    /// including writes from the most recently submitted frame
+    pub fn read_storage_buffer<T: StorageData>(
+        &self,
+        storage_buffer: &MutableStorageBufferHandle<T>,
+    ) -> anyhow::Result<Vec<T>> {
+        // a buffer may be written by any submitted frame,
+        // not only the most recent one
+        unsafe {
+            self.device
+                .wait_for_fences(&self.frames_in_flight, true, u64::MAX)?
+        };
+
+        let elements = self.storage_buffers.get_mapped_elements(storage_buffer);
+
+        Ok(elements.to_vec())
+    }
+
+    /// NOTE call this after draining gpu commands
+    pub fn drop_storage_buffer<T>(&mut self, storage_buffer: StorageBufferHandle<T>) {
+        let raw_storage_buffer = self.storage_buffers.take(storage_buffer);
+        self.destroy_storage_buffer(raw_storage_buffer);
+    }
+
+    /// NOTE call this after draining gpu commands
+    pub fn drop_mutable_storage_buffer<T>(
+        &mut self,
+        storage_buffer: MutableStorageBufferHandle<T>,
+    ) {
+        let raw_storage_buffer = self.storage_buffers.take_mutable(storage_buffer);
+        self.destroy_storage_buffer(raw_storage_buffer);
+    }
+
+    fn destroy_storage_buffer(&mut self, storage_buffer: RawStorageBuffer) {
+        unsafe {
+            self.device.destroy_buffer(storage_buffer.buffer, None);
+            self.device.free_memory(storage_buffer.device_mem, None);
+        }
+    }
+
+    pub fn create_pipeline<V: VertexDescription, I: VertexData, P>(
+        &mut self,
+        config: PipelineConfig<V, I, P>,
+    ) -> anyhow::Result<PipelineHandle<P>> {
         let pipeline = self.init_pipeline(config)?;
         let handle = self.pipelines.add(pipeline);
 
@@ -383,7 +693,7 @@ impl Renderer {
     }
 
     /// NOTE call this after draining gpu commands
-    pub fn drop_pipeline(&mut self, pipeline_handle: PipelineHandle) {
+    pub fn drop_pipeline<P>(&mut self, pipeline_handle: PipelineHandle<P>) {
         let pipeline = self.pipelines.take(pipeline_handle);
         self.destroy_pipeline(pipeline);
     }
@@ -399,11 +709,20 @@ impl Renderer {
                     .destroy_descriptor_set_layout(desc_set_layout, None);
             }
 
-            self.device.destroy_buffer(pipeline.index_buffer, None);
-            self.device.free_memory(pipeline.index_buffer_memory, None);
+            if let Some((index_buffer, index_buffer_memory)) = pipeline.index_buffer {
+                self.device.destroy_buffer(index_buffer, None);
+                self.device.free_memory(index_buffer_memory, None);
+            }
+
+            if let Some((instance_buffer, instance_buffer_memory)) = pipeline.instance_buffer {
+                self.device.destroy_buffer(instance_buffer, None);
+                self.device.free_memory(instance_buffer_memory, None);
+            }
 
-            self.device.destroy_buffer(pipeline.vertex_buffer, None);
-            self.device.free_memory(pipeline.vertex_buffer_memory, None);
+            if let Some((vertex_buffer, vertex_buffer_memory)) = pipeline.vertex_buffer {
+                self.device.destroy_buffer(vertex_buffer, None);
+                self.device.free_memory(vertex_buffer_memory, None);
+            }
 
             self.device.destroy_pipeline(pipeline.pipeline, None);
             self.device
@@ -411,12 +730,20 @@ impl Renderer {
         }
     }
 
-    fn init_pipeline<V: VertexDescription>(
+    fn init_pipeline<V: VertexDescription, I: VertexData, P>(
         &mut self,
-        config: PipelineConfig<V>,
+        config: PipelineConfig<V, I, P>,
     ) -> anyhow::Result<RendererPipeline> {
-        let pipeline_layout =
-            ShaderPipelineLayout::create_from_atlas(&self.device, &*config.shader)?;
+        let pipeline_layout = ShaderPipelineLayout::create_from_atlas(
+            &self.device,
+            &self.device_features,
+            &*config.shader,
+        )?;
+        device_features::check_vertex_formats(
+            &self.instance,
+            self.physical_device,
+            &config.shader.vertex_attribute_descriptions(),
+        )?;
         let pipeline = create_graphics_pipeline(
             &self.device,
             self.render_pass,
@@ -426,49 +753,60 @@ impl Renderer {
             &config.shader.vertex_attribute_descriptions(),
         )?;
 
-        let (vertex_buffer, vertex_buffer_memory) = create_vertex_buffer(
-            &self.instance,
-            &self.device,
-            self.physical_device,
-            self.command_pool,
-            self.graphics_queue,
-            &config.vertices,
-        )?;
+        // NOTE vulkan doesn't allow empty buffers
+        let vertex_buffer = if config.vertices.is_empty() {
+            None
+        } else {
+            Some(create_vertex_buffer(
+                &self.instance,
+                &self.device,
+                self.physical_device,
+                self.command_pool,
+                self.graphics_queue,
+                &config.vertices,
+            )?)
+        };
 
-        let (index_buffer, index_buffer_memory) = create_index_buffer(
-            &self.instance,
-            &self.device,
-            self.physical_device,
-            self.command_pool,
-            self.graphics_queue,
-            &config.indices,
-        )?;
+        let index_buffer = if config.indices.is_empty() {
+            None
+        } else {
+            Some(create_index_buffer(
+                &self.instance,
+                &self.device,
+                self.physical_device,
+                self.command_pool,
+                self.graphics_queue,
+                &config.indices,
+            )?)
+        };
+
+        let instance_buffer = if config.instances.is_empty() {
+            None
+        } else {
+            Some(create_vertex_buffer(
+                &self.instance,
+                &self.device,
+                self.physical_device,
+                self.command_pool,
+                self.graphics_queue,
+                &config.instances,
+            )?)
+        };
 
         let layout_bindings = config.shader.layout_bindings();
 
-        let descriptor_pool = create_descriptor_pool(&self.device, &pipeline_layout)?;
+        let push_constant_ranges = config.shader.pipeline_layout().push_constant_ranges_vk();
 
-        let textures = {
-            let mut textures = vec![];
-            for texture_handle in config.texture_handles {
-                let texture = self.textures.get(texture_handle);
-                textures.push(texture);
-            }
-            textures
-        };
+        let descriptor_pool = create_descriptor_pool(&self.device, &layout_bindings)?;
 
-        let uniform_buffers_in_layout_frame_order: Vec<&[RawUniformBuffer]> = config
-            .uniform_buffer_handles
-            .iter()
-            .map(|raw_handle| self.uniform_buffers.get_raw(raw_handle))
-            .collect();
+        self.create_attribute_samplers(&config.descriptor_set_handles)?;
+        let descriptor_resources = self.descriptor_resources(&config.descriptor_set_handles);
 
         let descriptor_sets = create_descriptor_sets(
             &self.device,
             descriptor_pool,
             &pipeline_layout.descriptor_set_layouts,
-            &uniform_buffers_in_layout_frame_order,
-            &textures,
+            &descriptor_resources,
             layout_bindings,
         )?;
 
@@ -476,19 +814,317 @@ impl Renderer {
             layout: pipeline_layout,
             pipeline,
             vertex_buffer,
-            vertex_buffer_memory,
             index_buffer,
-            index_buffer_memory,
+            instance_buffer,
             descriptor_pool,
             descriptor_sets,
             index_count: config.indices.len(),
+            vertex_count: config.vertex_count,
+            instance_count: config.instance_count,
+            push_constants: vec![0; push_constant_size(&push_constant_ranges)],
+            push_constant_ranges,
             shader: config.shader,
         })
     }
 
-    fn record_command_buffer(
+    /// creates any samplers from Sampler attributes that don't exist yet
+    fn create_attribute_samplers(
+        &mut self,
+        descriptor_set_handles: &[Vec<RawDescriptorHandle>],
+    ) -> anyhow::Result<()> {
+        for handle in descriptor_set_handles.iter().flatten() {
+            let settings = match handle {
+                RawDescriptorHandle::TextureWithSampler(_, settings)
+                | RawDescriptorHandle::SamplerSettings(settings) => settings,
+                _ => continue,
+            };
+
+            if self.samplers.get_for_settings(settings).is_some() {
+                continue;
+            }
+
+            let sampler = create_sampler(
+                &self.device,
+                self.physical_device_properties,
+                &self.device_features,
+                settings.filter,
+                settings.address_mode,
+            )?;
+            self.samplers.add_for_settings(*settings, sampler);
+        }
+
+        Ok(())
+    }
+
+    /// looks up the resources for each descriptor set binding
+    fn descriptor_resources(
+        &self,
+        descriptor_set_handles: &[Vec<RawDescriptorHandle>],
+    ) -> Vec<Vec<DescriptorResource<'_>>> {
+        descriptor_set_handles
+            .iter()
+            .map(|set_handles| {
+                set_handles
+                    .iter()
+                    .map(|handle| match handle {
+                        RawDescriptorHandle::UniformBuffer(raw_handle) => {
+                            DescriptorResource::UniformBuffer(
+                                self.uniform_buffers.get_raw(raw_handle),
+                            )
+                        }
+                        RawDescriptorHandle::StorageBuffer(raw_handle) => {
+                            DescriptorResource::StorageBuffer(
+                                self.storage_buffers.get_raw(raw_handle),
+                            )
+                        }
+                        RawDescriptorHandle::Texture(raw_handle) => {
+                            let texture = self.textures.get_raw(raw_handle);
+                            DescriptorResource::Texture(texture, texture.sampler)
+                        }
+                        RawDescriptorHandle::TextureWithSampler(raw_handle, settings) => {
+                            DescriptorResource::Texture(
+                                self.textures.get_raw(raw_handle),
+                                self.samplers.get_for_settings(settings).unwrap(),
+                            )
+                        }
+                        RawDescriptorHandle::Sampler(raw_handle) => {
+                            DescriptorResource::Sampler(self.samplers.get_raw(raw_handle))
+                        }
+                        RawDescriptorHandle::SamplerSettings(settings) => {
+                            DescriptorResource::Sampler(
+                                self.samplers.get_for_settings(settings).unwrap(),
+                            )
+                        }
+                        RawDescriptorHandle::BindlessTextures => {
+                            DescriptorResource::BindlessTextures(
+                                self.textures.bindless_textures().collect(),
+                            )
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn create_compute_pipeline<P>(
+        &mut self,
+        config: ComputePipelineConfig<P>,
+    ) -> anyhow::Result<ComputePipelineHandle<P>> {
+        let pipeline = self.init_compute_pipeline(config)?;
+        let handle = self.compute_pipelines.add(pipeline);
+
+        Ok(handle)
+    }
+
+    /// NOTE call this after draining gpu commands
+    pub fn drop_compute_pipeline<P>(&mut self, pipeline_handle: ComputePipelineHandle<P>) {
+        let pipeline = self.compute_pipelines.take(pipeline_handle);
+        self.destroy_compute_pipeline(pipeline);
+    }
+
+    fn destroy_compute_pipeline(&mut self, pipeline: RendererComputePipeline) {
+        unsafe {
+            // this also destroys the sets from the pool
+            self.device
+                .destroy_descriptor_pool(pipeline.descriptor_pool, None);
+
+            for &desc_set_layout in &pipeline.layout.descriptor_set_layouts {
+                self.device
+                    .destroy_descriptor_set_layout(desc_set_layout, None);
+            }
+
+            self.device.destroy_pipeline(pipeline.pipeline, None);
+            self.device
+                .destroy_pipeline_layout(pipeline.layout.pipeline_layout, None);
+        }
+    }
+
+    fn init_compute_pipeline<P>(
+        &mut self,
+        config: ComputePipelineConfig<P>,
+    ) -> anyhow::Result<RendererComputePipeline> {
+        let pipeline_layout = ComputeShaderPipelineLayout::create_from_atlas(
+            &self.device,
+            &self.device_features,
+            &*config.shader,
+        )?;
+        let pipeline = create_compute_pipeline(&self.device, &pipeline_layout)?;
+
+        let layout_bindings = config.shader.layout_bindings();
+
+        let push_constant_ranges = config.shader.pipeline_layout().push_constant_ranges_vk();
+
+        let descriptor_pool = create_descriptor_pool(&self.device, &layout_bindings)?;
+
+        self.create_attribute_samplers(&config.descriptor_set_handles)?;
+        let descriptor_resources = self.descriptor_resources(&config.descriptor_set_handles);
+
+        let descriptor_sets = create_descriptor_sets(
+            &self.device,
+            descriptor_pool,
+            &pipeline_layout.descriptor_set_layouts,
+            &descriptor_resources,
+            layout_bindings,
+        )?;
+
+        Ok(RendererComputePipeline {
+            layout: pipeline_layout,
+            pipeline,
+            descriptor_pool,
+            descriptor_sets,
+            push_constant_ranges,
+            shader: config.shader,
+        })
+    }
+
+    /// queues a compute dispatch with the given workgroup counts
+    /// NOTE this doesn't run anything yet; queued dispatches are recorded at the start of
+    /// the next draw_frame, in queue order and before any draws, so their writes are visible
+    /// to that frame's graphics work, and to read_storage_buffer after that frame is submitted
+    pub fn queue_dispatch(
+        &mut self,
+        pipeline_handle: &ComputePipelineHandle,
+        group_counts: [u32; 3],
+    ) {
+        self.compute_pipelines
+            .queue_dispatch(pipeline_handle, group_counts, None);
+    }
+
+    /// queues a compute dispatch like queue_dispatch,
+    /// recording the shader's push constants with it
+    pub fn queue_dispatch_with_push_constants<P: PushConstantData>(
+        &mut self,
+        pipeline_handle: &ComputePipelineHandle<P>,
+        group_counts: [u32; 3],
+        push_constants: P,
+    ) {
+        let bytes = bytemuck::bytes_of(&push_constants).to_vec();
+        self.compute_pipelines
+            .queue_dispatch(pipeline_handle, group_counts, Some(bytes));
+    }
+
+    /// pushes each of a pipeline's push constant ranges from the bytes of its PushConstants;
+    /// generated code asserts that the struct covers every range
+    fn record_push_constants(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        pipeline_layout: vk::PipelineLayout,
+        push_constant_ranges: &[vk::PushConstantRange],
+        push_constants: &[u8],
+    ) {
+        for range in push_constant_ranges {
+            let start = range.offset as usize;
+            let end = start + range.size as usize;
+
+            unsafe {
+                self.device.cmd_push_constants(
+                    command_buffer,
+                    pipeline_layout,
+                    range.stage_flags,
+                    range.offset,
+                    &push_constants[start..end],
+                );
+            }
+        }
+    }
+
+    fn record_dispatches(&mut self, command_buffer: vk::CommandBuffer) {
+        let pending_dispatches = self.compute_pipelines.take_pending_dispatches();
+        if pending_dispatches.is_empty() {
+            return;
+        }
+
+        // earlier frames' draws and dispatches may still be using buffers that compute writes
+        // reads only need an execution dependency, but earlier writes also need to be made
+        // available before they're read or overwritten here
+        let memory_barrier = vk::MemoryBarrier::default()
+            .src_access_mask(vk::AccessFlags::SHADER_WRITE)
+            .dst_access_mask(vk::AccessFlags::SHADER_READ | vk::AccessFlags::SHADER_WRITE);
+        unsafe {
+            self.device.cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::VERTEX_SHADER
+                    | vk::PipelineStageFlags::FRAGMENT_SHADER
+                    | vk::PipelineStageFlags::COMPUTE_SHADER,
+                vk::PipelineStageFlags::COMPUTE_SHADER,
+                vk::DependencyFlags::empty(),
+                &[memory_barrier],
+                &[],
+                &[],
+            );
+        }
+
+        for dispatch in pending_dispatches {
+            let compute_pipeline = self.compute_pipelines.get(&dispatch.handle);
+
+            // see create_descriptor_sets
+            let sets_per_frame = compute_pipeline.layout.descriptor_set_layouts.len();
+            let frame_start = self.current_frame * sets_per_frame;
+            let descriptor_sets =
+                &compute_pipeline.descriptor_sets[frame_start..frame_start + sets_per_frame];
+
+            let [x, y, z] = dispatch.group_counts;
+
+            unsafe {
+                self.device.cmd_bind_pipeline(
+                    command_buffer,
+                    vk::PipelineBindPoint::COMPUTE,
+                    compute_pipeline.pipeline,
+                );
+
+                self.device.cmd_bind_descriptor_sets(
+                    command_buffer,
+                    vk::PipelineBindPoint::COMPUTE,
+                    compute_pipeline.layout.pipeline_layout,
+                    0,
+                    descriptor_sets,
+                    &[],
+                );
+
+                if let Some(push_constants) = &dispatch.push_constants {
+                    self.record_push_constants(
+                        command_buffer,
+                        compute_pipeline.layout.pipeline_layout,
+                        &compute_pipeline.push_constant_ranges,
+                        push_constants,
+                    );
+                }
+
+                self.device.cmd_dispatch(command_buffer, x, y, z);
+            }
+
+            // make the writes visible to later dispatches and this frame's draws
+            let memory_barrier = vk::MemoryBarrier::default()
+                .src_access_mask(vk::AccessFlags::SHADER_WRITE)
+                .dst_access_mask(
+                    vk::AccessFlags::SHADER_READ
+                        | vk::AccessFlags::SHADER_WRITE
+                        | vk::AccessFlags::UNIFORM_READ
+                        | vk::AccessFlags::VERTEX_ATTRIBUTE_READ
+                        | vk::AccessFlags::INDEX_READ
+                        | vk::AccessFlags::INDIRECT_COMMAND_READ,
+                );
+            unsafe {
+                self.device.cmd_pipeline_barrier(
+                    command_buffer,
+                    vk::PipelineStageFlags::COMPUTE_SHADER,
+                    vk::PipelineStageFlags::COMPUTE_SHADER
+                        | vk::PipelineStageFlags::DRAW_INDIRECT
+                        | vk::PipelineStageFlags::VERTEX_INPUT
+                        | vk::PipelineStageFlags::VERTEX_SHADER
+                        | vk::PipelineStageFlags::FRAGMENT_SHADER,
+                    vk::DependencyFlags::empty(),
+                    &[memory_barrier],
+                    &[],
+                    &[],
+                );
+            }
+        }
+    }
+
+    fn record_command_buffer<P>(
         &mut self,
-        pipeline_handle: &PipelineHandle,
+        pipeline_handle: &PipelineHandle<P>,
         image_index: u32,
     ) -> Result<(), anyhow::Error> {
         let command_buffer = self.command_buffers[self.current_frame];
@@ -499,6 +1135,9 @@ impl Renderer {
                 .begin_command_buffer(command_buffer, &begin_info)?;
         }
 
+        // compute work happens outside the render pass
+        self.record_dispatches(command_buffer);
+
         let framebuffer = self.swapchain_framebuffers[image_index as usize];
         let render_area = vk::Rect2D::default()
             .offset(vk::Offset2D::default())
@@ -556,17 +1195,25 @@ impl Renderer {
         unsafe { self.device.cmd_set_scissor(command_buffer, 0, &scissors) };
 
         unsafe {
-            let buffers = [self.renderer_pipeline(pipeline_handle).vertex_buffer];
-            let offsets = [0];
-            self.device
-                .cmd_bind_vertex_buffers(command_buffer, 0, &buffers, &offsets);
+            let renderer_pipeline = self.renderer_pipeline(pipeline_handle);
+            if let Some((vertex_buffer, _)) = renderer_pipeline.vertex_buffer {
+                self.device
+                    .cmd_bind_vertex_buffers(command_buffer, 0, &[vertex_buffer], &[0]);
+            }
+            // per-instance data is always at binding 1
+            if let Some((instance_buffer, _)) = renderer_pipeline.instance_buffer {
+                self.device
+                    .cmd_bind_vertex_buffers(command_buffer, 1, &[instance_buffer], &[0]);
+            }
 
-            self.device.cmd_bind_index_buffer(
-                command_buffer,
-                self.renderer_pipeline(pipeline_handle).index_buffer,
-                0,
-                vk::IndexType::UINT32,
-            );
+            if let Some((index_buffer, _)) = renderer_pipeline.index_buffer {
+                self.device.cmd_bind_index_buffer(
+                    command_buffer,
+                    index_buffer,
+                    0,
+                    vk::IndexType::UINT32,
+                );
+            }
 
             let descriptor_sets = self.descriptor_sets_for_frame(pipeline_handle);
             self.device.cmd_bind_descriptor_sets(
@@ -580,20 +1227,64 @@ impl Renderer {
                 &[],
             );
 
-            let index_count = self.renderer_pipeline(pipeline_handle).index_count as u32;
-            self.device
-                .cmd_draw_indexed(command_buffer, index_count, 1, 0, 0, 0);
+            // see Gpu::push_constants
+            let renderer_pipeline = self.renderer_pipeline(pipeline_handle);
+            self.record_push_constants(
+                command_buffer,
+                renderer_pipeline.layout.pipeline_layout,
+                &renderer_pipeline.push_constant_ranges,
+                &renderer_pipeline.push_constants,
+            );
+
+            let instance_count = renderer_pipeline.instance_count;
+            if renderer_pipeline.index_buffer.is_some() {
+                let index_count = renderer_pipeline.index_count as u32;
+                self.device
+                    .cmd_draw_indexed(command_buffer, index_count, instance_count, 0, 0, 0);
+            } else {
+                let vertex_count = renderer_pipeline.vertex_count;
+                self.device
+                    .cmd_draw(command_buffer, vertex_count, instance_count, 0, 0);
+            }
         }
 
         // END RENDER PASS
         unsafe { self.device.cmd_end_render_pass(command_buffer) };
 
+        // mutable storage buffers are shared by every frame in flight,
+        // so shader writes are made visible to the next frame's shaders,
+        // and to the host after this frame's fence is signaled (see read_storage_buffer)
+        let memory_barrier = vk::MemoryBarrier::default()
+            .src_access_mask(vk::AccessFlags::SHADER_WRITE)
+            .dst_access_mask(
+                vk::AccessFlags::SHADER_READ
+                    | vk::AccessFlags::SHADER_WRITE
+                    | vk::AccessFlags::HOST_READ,
+            );
+        let shader_stages = vk::PipelineStageFlags::VERTEX_SHADER
+            | vk::PipelineStageFlags::FRAGMENT_SHADER
+            | vk::PipelineStageFlags::COMPUTE_SHADER;
+        unsafe {
+            self.device.cmd_pipeline_barrier(
+                command_buffer,
+                shader_stages,
+                shader_stages | vk::PipelineStageFlags::HOST,
+                vk::DependencyFlags::empty(),
+                &[memory_barrier],
+                &[],
+                &[],
+            );
+        }
+
         unsafe { self.device.end_command_buffer(command_buffer)? };
 
         Ok(())
     }
 
-    fn descriptor_sets_for_frame(&self, pipeline_handle: &PipelineHandle) -> &[vk::DescriptorSet] {
+    fn descriptor_sets_for_frame<P>(
+        &self,
+        pipeline_handle: &PipelineHandle<P>,
+    ) -> &[vk::DescriptorSet] {
         // see create_descriptor_sets
         let descriptor_sets_per_frame = self
             .renderer_pipeline(pipeline_handle)
@@ -607,9 +1298,9 @@ impl Renderer {
             .unwrap()
     }
 
-    pub fn draw_frame(
+    pub fn draw_frame<P>(
         &mut self,
-        pipeline_handle: &PipelineHandle,
+        pipeline_handle: &PipelineHandle<P>,
         gpu_update: impl FnOnce(&mut Gpu),
     ) -> Result<(), anyhow::Error> {
         self.total_frames += 1;
@@ -641,6 +1332,7 @@ impl Renderer {
         let mut gpu = Gpu {
             current_frame: self.current_frame,
             uniform_buffers: &mut self.uniform_buffers,
+            pipelines: &mut self.pipelines,
         };
         gpu_update(&mut gpu);
 
@@ -802,9 +1494,9 @@ impl Renderer {
     }
 
     #[cfg(debug_assertions)]
-    fn check_for_shader_recompile(
+    fn check_for_shader_recompile<P>(
         &mut self,
-        pipeline_handle: &PipelineHandle,
+        pipeline_handle: &PipelineHandle<P>,
     ) -> Result<(), anyhow::Error> {
         // drop old graphics reloaded pipelines for frames that are no longer needed
         let mut to_remove = vec![];
@@ -847,13 +1539,14 @@ impl Renderer {
 
     // shader hot reload
     #[cfg(debug_assertions)]
-    fn try_shader_recompile(
+    fn try_shader_recompile<P>(
         &mut self,
-        pipeline_handle: &PipelineHandle,
+        pipeline_handle: &PipelineHandle<P>,
         _edit_events: &[notify::Event],
     ) -> Result<(), anyhow::Error> {
         let mut tmp_pipeline_layout = match ShaderPipelineLayout::create_from_atlas(
             &self.device,
+            &self.device_features,
             &*self.renderer_pipeline(pipeline_handle).shader,
         ) {
             Ok(shaders) => shaders,
@@ -946,16 +1639,26 @@ impl Drop for Renderer {
             // NOTE the game 'should' clean these up,
             // but we try to be good gpu citizens
             for texture in self.textures.take_all() {
-                self.destroy_texture(texture);
+                self.destroy_texture(&texture);
+            }
+            self.destroy_texture(&self.bindless_fallback_texture);
+            for sampler in self.samplers.take_all() {
+                self.device.destroy_sampler(sampler, None);
             }
             for pipeline in self.pipelines.take_all() {
                 self.destroy_pipeline(pipeline);
             }
+            for compute_pipeline in self.compute_pipelines.take_all() {
+                self.destroy_compute_pipeline(compute_pipeline);
+            }
             for buffers_per_frame in self.uniform_buffers.take_all() {
                 for uniform_buffer in buffers_per_frame {
                     self.destroy_uniform_buffer(uniform_buffer);
                 }
             }
+            for storage_buffer in self.storage_buffers.take_all() {
+                self.destroy_storage_buffer(storage_buffer);
+            }
 
             self.device.destroy_device(None);
 
@@ -1064,7 +1767,11 @@ impl QueueFamilyIndices {
 
         for (i, family) in queue_families.iter().enumerate() {
             // NOTE this also implies vk::QueueFlags::TRANSFER
-            if family.queue_flags.contains(vk::QueueFlags::GRAPHICS) {
+            // compute dispatches are recorded in the same command buffers as draws
+            if family
+                .queue_flags
+                .contains(vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE)
+            {
                 graphics = Some(i as u32);
             }
 
@@ -1102,6 +1809,15 @@ const REQUIRED_DEVICE_EXTENSIONS: [&CStr; 2] = [
     vk::KHR_SHADER_DRAW_PARAMETERS_NAME,
 ];
 
+fn required_device_extensions() -> Vec<&'static CStr> {
+    let mut extensions = REQUIRED_DEVICE_EXTENSIONS.to_vec();
+    if shaders::SCALAR_BLOCK_LAYOUT {
+        extensions.push(vk::EXT_SCALAR_BLOCK_LAYOUT_NAME);
+    }
+
+    extensions
+}
+
 fn choose_physical_device(
     instance: &ash::Instance,
     surface_ext: &ash::khr::surface::Instance,
@@ -1110,6 +1826,7 @@ fn choose_physical_device(
     vk::PhysicalDevice,
     QueueFamilyIndices,
     vk::PhysicalDeviceProperties,
+    DeviceFeatures,
 )> {
     let physical_devices: Vec<vk::PhysicalDevice> =
         unsafe { instance.enumerate_physical_devices()? };
@@ -1122,8 +1839,11 @@ fn choose_physical_device(
             continue;
         };
 
-        let supports_extensions =
-            check_device_extension_support(instance, physical_device, &REQUIRED_DEVICE_EXTENSIONS)?;
+        let supports_extensions = check_device_extension_support(
+            instance,
+            physical_device,
+            &required_device_extensions(),
+        )?;
         if !supports_extensions {
             continue;
         }
@@ -1136,17 +1856,13 @@ fn choose_physical_device(
             continue;
         }
 
-        let features = unsafe { instance.get_physical_device_features(physical_device) };
-        if features.sampler_anisotropy != vk::TRUE {
-            continue;
-        }
-
         let props = unsafe { instance.get_physical_device_properties(physical_device) };
+        let device_features = DeviceFeatures::query(instance, physical_device);
 
-        devices_with_indices_and_props.push((physical_device, indices, props));
+        devices_with_indices_and_props.push((physical_device, indices, props, device_features));
     }
 
-    devices_with_indices_and_props.sort_by_key(|(_physical_device, _indices, props)| {
+    devices_with_indices_and_props.sort_by_key(|(_physical_device, _indices, props, _features)| {
         match props.device_type {
             vk::PhysicalDeviceType::DISCRETE_GPU => 0,
             vk::PhysicalDeviceType::INTEGRATED_GPU => 1,
@@ -1326,6 +2042,7 @@ fn create_logical_device(
     instance: &ash::Instance,
     physical_device: vk::PhysicalDevice,
     indices: &QueueFamilyIndices,
+    device_features: &DeviceFeatures,
 ) -> Result<ash::Device, anyhow::Error> {
     let unique_queue_families = BTreeSet::from([indices.graphics, indices.presentation]);
 
@@ -1340,32 +2057,64 @@ fn create_logical_device(
     }
 
     let mut features = vk::PhysicalDeviceFeatures::default()
-        .sampler_anisotropy(true)
-        .sample_rate_shading(ENABLE_SAMPLE_SHADING);
+        .sampler_anisotropy(device_features.sampler_anisotropy)
+        .sample_rate_shading(ENABLE_SAMPLE_SHADING)
+        // writes to RWStructuredBuffers from fragment shaders (also used by shader println)
+        .fragment_stores_and_atomics(true)
+        // features used by shader types; shaders that need unsupported ones fail to load
+        .shader_float64(device_features.shader_float64)
+        .shader_int64(device_features.shader_int64);
     if cfg!(debug_assertions) {
-        // features used by shader println
-        features = features
-            .fragment_stores_and_atomics(true)
-            .vertex_pipeline_stores_and_atomics(true)
-            .shader_int64(true);
+        // features used by shader println (which also needs shader_int64)
+        features = features.vertex_pipeline_stores_and_atomics(true);
     }
 
-    let enabled_extension_names: Vec<_> = REQUIRED_DEVICE_EXTENSIONS
+    let required_extensions = required_device_extensions();
+    let enabled_extension_names: Vec<_> = required_extensions
         .iter()
         .map(|cstr| cstr.as_ptr())
         .collect();
 
-    #[cfg(not(debug_assertions))]
-    let create_info = vk::DeviceCreateInfo::default()
-        .queue_create_infos(&queue_create_infos)
-        .enabled_features(&features)
-        .enabled_extension_names(&enabled_extension_names);
-
-    #[cfg(debug_assertions)]
     let mut create_info = vk::DeviceCreateInfo::default()
         .queue_create_infos(&queue_create_infos)
         .enabled_features(&features)
         .enabled_extension_names(&enabled_extension_names);
+
+    let mut scalar_block_layout_features =
+        vk::PhysicalDeviceScalarBlockLayoutFeatures::default().scalar_block_layout(true);
+    if shaders::SCALAR_BLOCK_LAYOUT {
+        create_info = create_info.push_next(&mut scalar_block_layout_features);
+    }
+    // features used by bindless texture arrays; shaders that use them fail to load without them
+    let mut descriptor_indexing_features = vk::PhysicalDeviceDescriptorIndexingFeatures::default()
+        .runtime_descriptor_array(true)
+        .shader_sampled_image_array_non_uniform_indexing(true)
+        .descriptor_binding_partially_bound(true)
+        .descriptor_binding_sampled_image_update_after_bind(true)
+        .descriptor_binding_update_unused_while_pending(true);
+    if device_features.descriptor_indexing {
+        create_info = create_info.push_next(&mut descriptor_indexing_features);
+    }
+    // features used by pointer fields; shaders that use them fail to load without them
+    // NOTE shader println always needs them in debug builds
+    let mut buffer_device_address_features =
+        vk::PhysicalDeviceBufferDeviceAddressFeatures::default().buffer_device_address(true);
+    if device_features.buffer_device_address || cfg!(debug_assertions) {
+        create_info = create_info.push_next(&mut buffer_device_address_features);
+    }
+    // features used by half
+    let mut float16_int8_features = vk::PhysicalDeviceShaderFloat16Int8Features::default()
+        .shader_float16(device_features.shader_float16);
+    let mut sixteen_bit_storage_features = vk::PhysicalDevice16BitStorageFeatures::default()
+        .storage_buffer16_bit_access(device_features.storage_buffer16_bit_access)
+        .uniform_and_storage_buffer16_bit_access(
+            device_features.uniform_and_storage_buffer16_bit_access,
+        )
+        .storage_push_constant16(device_features.storage_push_constant16)
+        .storage_input_output16(device_features.storage_input_output16);
+    create_info = create_info
+        .push_next(&mut float16_int8_features)
+        .push_next(&mut sixteen_bit_storage_features);
     // features used by shader println
     #[cfg(debug_assertions)]
     let mut timeline_semaphore_features =
@@ -1375,9 +2124,6 @@ fn create_logical_device(
         .vulkan_memory_model(true)
         .vulkan_memory_model_device_scope(true);
     #[cfg(debug_assertions)]
-    let mut buffer_device_address_features =
-        vk::PhysicalDeviceBufferDeviceAddressFeatures::default().buffer_device_address(true);
-    #[cfg(debug_assertions)]
     let mut eight_bit_storage_features =
         vk::PhysicalDevice8BitStorageFeatures::default().storage_buffer8_bit_access(true);
     #[cfg(debug_assertions)]
@@ -1385,7 +2131,6 @@ fn create_logical_device(
         create_info = create_info
             .push_next(&mut timeline_semaphore_features)
             .push_next(&mut memory_model_features)
-            .push_next(&mut buffer_device_address_features)
             .push_next(&mut eight_bit_storage_features);
     }
 
@@ -1438,8 +2183,15 @@ fn create_swapchain_image_views(
 ) -> Result<Vec<vk::ImageView>, anyhow::Error> {
     let mut swapchain_image_views = Vec::with_capacity(swapchain_images.len());
     for &image in swapchain_images {
-        let image_view =
-            create_image_view(device, image, image_format, vk::ImageAspectFlags::COLOR, 1)?;
+        let image_view = create_image_view(
+            device,
+            image,
+            image_format,
+            vk::ImageAspectFlags::COLOR,
+            vk::ImageViewType::TYPE_2D,
+            1,
+            1,
+        )?;
         swapchain_image_views.push(image_view);
     }
 
@@ -1567,23 +2319,42 @@ fn create_graphics_pipeline(
     vertex_attribute_descriptions: &[vk::VertexInputAttributeDescription],
 ) -> Result<vk::Pipeline, anyhow::Error> {
     let vert_shader_spv = &pipeline_layout.vertex_shader.spv_bytes;
-    let frag_shader_spv = &pipeline_layout.fragment_shader.spv_bytes;
-
     let vert_create_info = vk::ShaderModuleCreateInfo::default().code(vert_shader_spv);
-    let frag_create_info = vk::ShaderModuleCreateInfo::default().code(frag_shader_spv);
-
     let vert_shader = unsafe { device.create_shader_module(&vert_create_info, None)? };
-    let frag_shader = unsafe { device.create_shader_module(&frag_create_info, None)? };
 
-    let vert_create_info = vk::PipelineShaderStageCreateInfo::default()
+    // the same constants are given to every stage; unused ids are ignored
+    let specialization_info = pipeline_layout.specialization_constants.vk_info();
+
+    let mut vert_create_info = vk::PipelineShaderStageCreateInfo::default()
         .stage(vk::ShaderStageFlags::VERTEX)
         .module(vert_shader)
         .name(&pipeline_layout.vertex_shader.entry_point_name);
-    let frag_create_info = vk::PipelineShaderStageCreateInfo::default()
-        .stage(vk::ShaderStageFlags::FRAGMENT)
-        .module(frag_shader)
-        .name(&pipeline_layout.fragment_shader.entry_point_name);
-    let stages = [vert_create_info, frag_create_info];
+    if !pipeline_layout.specialization_constants.is_empty() {
+        vert_create_info = vert_create_info.specialization_info(&specialization_info);
+    }
+    let mut stages = vec![vert_create_info];
+
+    // depth-only pipelines (ie, shadow maps or a depth prepass) have no fragment shader
+    let frag_shader = match &pipeline_layout.fragment_shader {
+        Some(fragment_shader) => {
+            let frag_create_info =
+                vk::ShaderModuleCreateInfo::default().code(&fragment_shader.spv_bytes);
+            let frag_shader = unsafe { device.create_shader_module(&frag_create_info, None)? };
+
+            let mut frag_create_info = vk::PipelineShaderStageCreateInfo::default()
+                .stage(vk::ShaderStageFlags::FRAGMENT)
+                .module(frag_shader)
+                .name(&fragment_shader.entry_point_name);
+            if !pipeline_layout.specialization_constants.is_empty() {
+                frag_create_info = frag_create_info.specialization_info(&specialization_info);
+            }
+            stages.push(frag_create_info);
+
+            Some(frag_shader)
+        }
+
+        None => None,
+    };
 
     let dynamic_states = vec![vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
     let dynamic_state =
@@ -1616,10 +2387,16 @@ fn create_graphics_pipeline(
         .min_sample_shading(if ENABLE_SAMPLE_SHADING { 0.2 } else { 0.0 })
         .rasterization_samples(msaa_samples);
 
+    // without a fragment shader the color output is undefined, so it's not written
+    let color_write_mask = if pipeline_layout.fragment_shader.is_some() {
+        vk::ColorComponentFlags::RGBA
+    } else {
+        vk::ColorComponentFlags::empty()
+    };
     // color blend per attached framebuffer
     let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
         .blend_enable(false)
-        .color_write_mask(vk::ColorComponentFlags::RGBA);
+        .color_write_mask(color_write_mask);
     let color_attachments = [color_blend_attachment];
     // global color blending
     let color_blend_state = vk::PipelineColorBlendStateCreateInfo::default()
@@ -1654,12 +2431,48 @@ fn create_graphics_pipeline(
     };
     let graphics_pipeline = graphics_pipelines[0];
 
-    unsafe { device.destroy_shader_module(frag_shader, None) };
+    if let Some(frag_shader) = frag_shader {
+        unsafe { device.destroy_shader_module(frag_shader, None) };
+    }
     unsafe { device.destroy_shader_module(vert_shader, None) };
 
     Ok(graphics_pipeline)
 }
 
+fn create_compute_pipeline(
+    device: &ash::Device,
+    pipeline_layout: &ComputeShaderPipelineLayout,
+) -> Result<vk::Pipeline, anyhow::Error> {
+    let comp_shader_spv = &pipeline_layout.compute_shader.spv_bytes;
+    let comp_create_info = vk::ShaderModuleCreateInfo::default().code(comp_shader_spv);
+    let comp_shader = unsafe { device.create_shader_module(&comp_create_info, None)? };
+
+    let specialization_info = pipeline_layout.specialization_constants.vk_info();
+
+    let mut stage = vk::PipelineShaderStageCreateInfo::default()
+        .stage(vk::ShaderStageFlags::COMPUTE)
+        .module(comp_shader)
+        .name(&pipeline_layout.compute_shader.entry_point_name);
+    if !pipeline_layout.specialization_constants.is_empty() {
+        stage = stage.specialization_info(&specialization_info);
+    }
+
+    let pipeline_info = vk::ComputePipelineCreateInfo::default()
+        .stage(stage)
+        .layout(pipeline_layout.pipeline_layout);
+
+    let compute_pipelines = unsafe {
+        device
+            .create_compute_pipelines(vk::PipelineCache::null(), &[pipeline_info], None)
+            .map_err(|e| anyhow::anyhow!("failed to create compute pipelines: {e:?}"))?
+    };
+    let compute_pipeline = compute_pipelines[0];
+
+    unsafe { device.destroy_shader_module(comp_shader, None) };
+
+    Ok(compute_pipeline)
+}
+
 fn create_framebuffers(
     device: &ash::Device,
     render_pass: vk::RenderPass,
@@ -1742,7 +2555,7 @@ fn create_sync_objects(
     Ok((image_available, render_finished, frames_in_flight))
 }
 
-fn create_vertex_buffer<V: GPUWrite>(
+fn create_vertex_buffer<V: VertexData>(
     instance: &ash::Instance,
     device: &ash::Device,
     physical_device: vk::PhysicalDevice,
@@ -1761,7 +2574,7 @@ fn create_vertex_buffer<V: GPUWrite>(
         vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
     )?;
 
-    unsafe { write_to_gpu_buffer(device, staging_buffer_memory, vertices)? };
+    write_to_gpu_buffer(device, staging_buffer_memory, vertices)?;
 
     let (vertex_buffer, vertex_buffer_memory) = create_memory_buffer(
         instance,
@@ -1807,7 +2620,7 @@ fn create_index_buffer(
         vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
     )?;
 
-    unsafe { write_to_gpu_buffer(device, staging_buffer_memory, indices)? };
+    write_to_gpu_buffer(device, staging_buffer_memory, indices)?;
 
     let (index_buffer, index_buffer_memory) = create_memory_buffer(
         instance,
@@ -1835,6 +2648,129 @@ fn create_index_buffer(
     Ok((index_buffer, index_buffer_memory))
 }
 
+fn create_storage_buffer<T: StorageData>(
+    instance: &ash::Instance,
+    device: &ash::Device,
+    physical_device: vk::PhysicalDevice,
+    command_pool: vk::CommandPool,
+    graphics_queue: vk::Queue,
+    elements: &[T],
+    buffer_device_address: bool,
+) -> Result<RawStorageBuffer, anyhow::Error> {
+    // NOTE vulkan doesn't allow empty buffers
+    if elements.is_empty() {
+        anyhow::bail!("storage buffers need at least one element");
+    }
+
+    let buffer_size = std::mem::size_of_val(elements) as u64;
+
+    let (staging_buffer, staging_buffer_memory) = create_memory_buffer(
+        instance,
+        device,
+        physical_device,
+        buffer_size,
+        vk::BufferUsageFlags::TRANSFER_SRC,
+        vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+    )?;
+
+    write_to_gpu_buffer(device, staging_buffer_memory, elements)?;
+
+    let (storage_buffer, storage_buffer_memory) = create_memory_buffer(
+        instance,
+        device,
+        physical_device,
+        buffer_size,
+        vk::BufferUsageFlags::TRANSFER_DST
+            | vk::BufferUsageFlags::STORAGE_BUFFER
+            | storage_buffer_address_usage(buffer_device_address),
+        vk::MemoryPropertyFlags::DEVICE_LOCAL,
+    )?;
+
+    copy_memory_buffer(
+        device,
+        command_pool,
+        staging_buffer,
+        storage_buffer,
+        buffer_size,
+        graphics_queue,
+    )?;
+
+    unsafe {
+        device.destroy_buffer(staging_buffer, None);
+        device.free_memory(staging_buffer_memory, None);
+    }
+
+    let device_address =
+        buffer_device_address.then(|| get_buffer_device_address(device, storage_buffer));
+
+    Ok(RawStorageBuffer {
+        buffer: storage_buffer,
+        device_mem: storage_buffer_memory,
+        size: buffer_size,
+        device_address,
+        mapped_mem: None,
+    })
+}
+
+fn create_mutable_storage_buffer<T: StorageData>(
+    instance: &ash::Instance,
+    device: &ash::Device,
+    physical_device: vk::PhysicalDevice,
+    elements: &[T],
+    buffer_device_address: bool,
+) -> Result<RawStorageBuffer, anyhow::Error> {
+    // NOTE vulkan doesn't allow empty buffers
+    if elements.is_empty() {
+        anyhow::bail!("storage buffers need at least one element");
+    }
+
+    let buffer_size = std::mem::size_of_val(elements) as u64;
+
+    // NOTE this stays host-visible so that it can be read back without a staging copy
+    let (storage_buffer, storage_buffer_memory) = create_memory_buffer(
+        instance,
+        device,
+        physical_device,
+        buffer_size,
+        vk::BufferUsageFlags::STORAGE_BUFFER | storage_buffer_address_usage(buffer_device_address),
+        vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+    )?;
+
+    let device_address =
+        buffer_device_address.then(|| get_buffer_device_address(device, storage_buffer));
+
+    let bytes: &[u8] = bytemuck::cast_slice(elements);
+    let mapped_mem = unsafe {
+        let mapped_mem =
+            device.map_memory(storage_buffer_memory, 0, buffer_size, Default::default())?;
+        std::slice::from_raw_parts_mut(mapped_mem as *mut u8, bytes.len()).copy_from_slice(bytes);
+        mapped_mem
+    };
+
+    Ok(RawStorageBuffer {
+        buffer: storage_buffer,
+        device_mem: storage_buffer_memory,
+        size: buffer_size,
+        device_address,
+        mapped_mem: Some(mapped_mem),
+    })
+}
+
+/// storage buffers only have device addresses when the device supports them
+fn storage_buffer_address_usage(buffer_device_address: bool) -> vk::BufferUsageFlags {
+    if buffer_device_address {
+        vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS
+    } else {
+        vk::BufferUsageFlags::empty()
+    }
+}
+
+fn get_buffer_device_address(device: &ash::Device, buffer: vk::Buffer) -> vk::DeviceAddress {
+    let address_info = vk::BufferDeviceAddressInfo::default().buffer(buffer);
+
+    unsafe { device.get_buffer_device_address(&address_info) }
+}
+
 fn copy_memory_buffer(
     device: &ash::Device,
     command_pool: vk::CommandPool,
@@ -1877,9 +2813,15 @@ fn create_memory_buffer(
         memory_requirements.memory_type_bits,
         memory_property_flags,
     )?;
-    let allocate_info = vk::MemoryAllocateInfo::default()
+    let mut allocate_info = vk::MemoryAllocateInfo::default()
         .allocation_size(memory_requirements.size)
         .memory_type_index(mem_type_index as u32);
+    // buffers with device addresses need memory allocated with the matching flag
+    let mut allocate_flags_info =
+        vk::MemoryAllocateFlagsInfo::default().flags(vk::MemoryAllocateFlags::DEVICE_ADDRESS);
+    if buffer_usage.contains(vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS) {
+        allocate_info = allocate_info.push_next(&mut allocate_flags_info);
+    }
     let buffer_memory = unsafe { device.allocate_memory(&allocate_info, None)? };
 
     unsafe {
@@ -1911,21 +2853,76 @@ fn find_memory_type_index(
     Err(anyhow::anyhow!("failed to find suitable memory type"))
 }
 
+/// layout_bindings has one set of descriptions per descriptor set
 fn create_descriptor_pool(
     device: &ash::Device,
-    pipeline_layout: &ShaderPipelineLayout,
+    layout_bindings: &[Vec<LayoutDescription>],
 ) -> Result<vk::DescriptorPool, anyhow::Error> {
-    let descriptor_sets_per_frame = pipeline_layout.descriptor_set_layouts.len() as u32;
+    let descriptor_sets_per_frame = layout_bindings.len() as u32;
     let descriptor_set_count = descriptor_sets_per_frame * MAX_FRAMES_IN_FLIGHT as u32;
-    let uniform_buffer_pool_size = vk::DescriptorPoolSize::default()
-        .ty(vk::DescriptorType::UNIFORM_BUFFER)
-        .descriptor_count(descriptor_set_count);
-    let sampler_pool_size = vk::DescriptorPoolSize::default()
-        .ty(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
-        .descriptor_count(descriptor_set_count);
-
-    let pool_sizes = [uniform_buffer_pool_size, sampler_pool_size];
+
+    let mut uniform_buffer_count = 0;
+    let mut combined_sampler_count = 0;
+    let mut sampled_image_count = 0;
+    let mut sampler_count = 0;
+    let mut storage_buffer_count = 0;
+    let mut storage_texel_buffer_count = 0;
+    let mut update_after_bind = false;
+    for description in layout_bindings.iter().flatten() {
+        match description {
+            LayoutDescription::Uniform(d) => uniform_buffer_count += d.descriptor_count,
+            LayoutDescription::Texture(d) => combined_sampler_count += d.descriptor_count,
+            LayoutDescription::SampledTexture(d) => sampled_image_count += d.descriptor_count,
+            LayoutDescription::Sampler(d) => sampler_count += d.descriptor_count,
+            LayoutDescription::StorageBuffer(d) => storage_buffer_count += d.descriptor_count,
+            LayoutDescription::StorageTexelBuffer(d) => {
+                storage_texel_buffer_count += d.descriptor_count
+            }
+            LayoutDescription::BindlessTextures(d) => {
+                update_after_bind = true;
+                match d.descriptor_type {
+                    vk::DescriptorType::COMBINED_IMAGE_SAMPLER => {
+                        combined_sampler_count += d.descriptor_count
+                    }
+                    _ => sampled_image_count += d.descriptor_count,
+                }
+            }
+        }
+    }
+
+    let pool_sizes: Vec<vk::DescriptorPoolSize> = [
+        (vk::DescriptorType::UNIFORM_BUFFER, uniform_buffer_count),
+        (
+            vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+            combined_sampler_count,
+        ),
+        (vk::DescriptorType::SAMPLED_IMAGE, sampled_image_count),
+        (vk::DescriptorType::SAMPLER, sampler_count),
+        (vk::DescriptorType::STORAGE_BUFFER, storage_buffer_count),
+        (
+            vk::DescriptorType::STORAGE_TEXEL_BUFFER,
+            storage_texel_buffer_count,
+        ),
+    ]
+    .into_iter()
+    // zero-sized pools are invalid
+    .filter(|(_ty, count)| *count > 0)
+    .map(|(ty, count)| {
+        vk::DescriptorPoolSize::default()
+            .ty(ty)
+            .descriptor_count(count * MAX_FRAMES_IN_FLIGHT as u32)
+    })
+    .collect();
+
+    // required for sets with update-after-bind layouts
+    let flags = if update_after_bind {
+        vk::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND
+    } else {
+        vk::DescriptorPoolCreateFlags::empty()
+    };
+
     let pool_create_info = vk::DescriptorPoolCreateInfo::default()
+        .flags(flags)
         .pool_sizes(&pool_sizes)
         .max_sets(descriptor_set_count);
 
@@ -1937,7 +2934,19 @@ fn create_descriptor_pool(
 #[derive(Debug)]
 pub enum LayoutDescription {
     Uniform(UniformBufferDescription),
+    /// a texture with its own sampler, ie Sampler2D
     Texture(TextureDescription),
+    /// a texture without a sampler, ie Texture2D
+    SampledTexture(TextureDescription),
+    /// a separate SamplerState
+    Sampler(SamplerDescription),
+    StorageBuffer(StorageBufferDescription),
+    /// a RWBuffer<T>, which has a format rather than a struct layout
+    // NOTE there's no generated handle for these yet,
+    // so a pipeline using one fails in create_descriptor_sets
+    StorageTexelBuffer(StorageBufferDescription),
+    /// an unbounded texture array, filled from the renderer's bindless table
+    BindlessTextures(BindlessTextureDescription),
 }
 
 #[derive(Debug)]
@@ -1948,6 +2957,13 @@ pub struct UniformBufferDescription {
     pub descriptor_count: u32,
 }
 
+#[derive(Debug)]
+pub struct StorageBufferDescription {
+    pub binding: u32,
+    // the number of descriptors in the descriptor set
+    pub descriptor_count: u32,
+}
+
 #[derive(Debug)]
 pub struct TextureDescription {
     pub layout: vk::ImageLayout,
@@ -1956,14 +2972,61 @@ pub struct TextureDescription {
     pub descriptor_count: u32,
 }
 
+#[derive(Debug)]
+pub struct BindlessTextureDescription {
+    pub layout: vk::ImageLayout,
+    pub binding: u32,
+    /// either COMBINED_IMAGE_SAMPLER or SAMPLED_IMAGE
+    pub descriptor_type: vk::DescriptorType,
+    // the number of descriptors in the descriptor set
+    pub descriptor_count: u32,
+}
+
+#[derive(Debug)]
+pub struct SamplerDescription {
+    pub binding: u32,
+    // the number of descriptors in the descriptor set
+    pub descriptor_count: u32,
+}
+
+/// the renderer resource for one descriptor set binding
+enum DescriptorResource<'a> {
+    /// uniform buffers are per-frame
+    UniformBuffer(&'a [RawUniformBuffer]),
+    StorageBuffer(&'a RawStorageBuffer),
+    /// the sampler is the texture's own, unless set by a Sampler attribute
+    Texture(&'a Texture, vk::Sampler),
+    Sampler(vk::Sampler),
+    /// every live texture, with its bindless index
+    BindlessTextures(Vec<(usize, &'a Texture)>),
+}
+
 fn create_descriptor_sets(
     device: &ash::Device,
     descriptor_pool: vk::DescriptorPool,
     descriptor_set_layouts: &[vk::DescriptorSetLayout],
-    uniform_buffers_in_layout_frame_order: &[&[RawUniformBuffer]],
-    textures: &[&Texture],
+    descriptor_resources: &[Vec<DescriptorResource>],
     layout_bindings: Vec<Vec<LayoutDescription>>,
 ) -> Result<Vec<vk::DescriptorSet>, anyhow::Error> {
+    if descriptor_resources.len() != layout_bindings.len() {
+        anyhow::bail!(
+            "expected resources for {} descriptor sets, got {}",
+            layout_bindings.len(),
+            descriptor_resources.len()
+        );
+    }
+    for (set_index, (layout_descriptions, set_resources)) in
+        layout_bindings.iter().zip(descriptor_resources).enumerate()
+    {
+        if set_resources.len() != layout_descriptions.len() {
+            anyhow::bail!(
+                "expected {} resources for descriptor set {set_index}, got {}",
+                layout_descriptions.len(),
+                set_resources.len()
+            );
+        }
+    }
+
     // this vec and the resulting vec of descriptor sets are arranged like this:
     // [
     //     frame_0_set_0,
@@ -1988,12 +3051,14 @@ fn create_descriptor_sets(
             let ds = frame * descriptor_set_layouts.len() + layout_offset;
             let dst_set = descriptor_sets[ds];
             let layout_descriptions = &layout_bindings[layout_offset];
-
-            for description in layout_descriptions {
-                match description {
-                    LayoutDescription::Uniform(uniform_buffer_description) => {
-                        let raw_uniform_buffers_by_frame =
-                            uniform_buffers_in_layout_frame_order[layout_offset];
+            let set_resources = &descriptor_resources[layout_offset];
+
+            for (description, resource) in layout_descriptions.iter().zip(set_resources) {
+                match (description, resource) {
+                    (
+                        LayoutDescription::Uniform(uniform_buffer_description),
+                        DescriptorResource::UniformBuffer(raw_uniform_buffers_by_frame),
+                    ) => {
                         let uniform_buffer = raw_uniform_buffers_by_frame[frame].buffer;
 
                         let buffer_info = vk::DescriptorBufferInfo::default()
@@ -2013,13 +3078,14 @@ fn create_descriptor_sets(
                         unsafe { device.update_descriptor_sets(&writes, &[]) };
                     }
 
-                    LayoutDescription::Texture(texture_description) => {
-                        let texture = textures[layout_offset];
-
+                    (
+                        LayoutDescription::Texture(texture_description),
+                        DescriptorResource::Texture(texture, sampler),
+                    ) => {
                         let image_info = vk::DescriptorImageInfo::default()
                             .image_layout(texture_description.layout)
                             .image_view(texture.image_view)
-                            .sampler(texture.sampler);
+                            .sampler(*sampler);
                         let image_info = [image_info];
                         let image_write = vk::WriteDescriptorSet::default()
                             .dst_set(dst_set)
@@ -2032,6 +3098,87 @@ fn create_descriptor_sets(
                         let writes = [image_write];
                         unsafe { device.update_descriptor_sets(&writes, &[]) };
                     }
+
+                    (
+                        LayoutDescription::SampledTexture(texture_description),
+                        DescriptorResource::Texture(texture, _),
+                    ) => {
+                        let image_info = vk::DescriptorImageInfo::default()
+                            .image_layout(texture_description.layout)
+                            .image_view(texture.image_view);
+                        let image_info = [image_info];
+                        let image_write = vk::WriteDescriptorSet::default()
+                            .dst_set(dst_set)
+                            .dst_binding(texture_description.binding)
+                            .dst_array_element(0)
+                            .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                            .descriptor_count(texture_description.descriptor_count)
+                            .image_info(&image_info);
+
+                        let writes = [image_write];
+                        unsafe { device.update_descriptor_sets(&writes, &[]) };
+                    }
+
+                    (
+                        LayoutDescription::Sampler(sampler_description),
+                        DescriptorResource::Sampler(sampler),
+                    ) => {
+                        let image_info = vk::DescriptorImageInfo::default().sampler(*sampler);
+                        let image_info = [image_info];
+                        let sampler_write = vk::WriteDescriptorSet::default()
+                            .dst_set(dst_set)
+                            .dst_binding(sampler_description.binding)
+                            .dst_array_element(0)
+                            .descriptor_type(vk::DescriptorType::SAMPLER)
+                            .descriptor_count(sampler_description.descriptor_count)
+                            .image_info(&image_info);
+
+                        let writes = [sampler_write];
+                        unsafe { device.update_descriptor_sets(&writes, &[]) };
+                    }
+
+                    (
+                        LayoutDescription::StorageBuffer(storage_buffer_description),
+                        DescriptorResource::StorageBuffer(storage_buffer),
+                    ) => {
+                        // storage buffers are shared between frames
+                        let buffer_info = vk::DescriptorBufferInfo::default()
+                            .offset(0)
+                            .buffer(storage_buffer.buffer)
+                            .range(storage_buffer.size);
+                        let buffer_info = [buffer_info];
+                        let storage_buffer_write = vk::WriteDescriptorSet::default()
+                            .dst_set(dst_set)
+                            .dst_binding(storage_buffer_description.binding)
+                            .dst_array_element(0)
+                            .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                            .descriptor_count(storage_buffer_description.descriptor_count)
+                            .buffer_info(&buffer_info);
+
+                        let writes = [storage_buffer_write];
+                        unsafe { device.update_descriptor_sets(&writes, &[]) };
+                    }
+
+                    (
+                        LayoutDescription::BindlessTextures(bindless_description),
+                        DescriptorResource::BindlessTextures(textures),
+                    ) => {
+                        for &(index, texture) in textures {
+                            write_bindless_texture(
+                                device,
+                                dst_set,
+                                bindless_description,
+                                index,
+                                texture,
+                            );
+                        }
+                    }
+
+                    (description, _) => {
+                        anyhow::bail!(
+                            "mismatched resource for descriptor set {layout_offset}: {description:?}"
+                        );
+                    }
                 }
             }
         }
@@ -2040,18 +3187,45 @@ fn create_descriptor_sets(
     Ok(descriptor_sets)
 }
 
-fn create_texture(
+fn write_bindless_texture(
+    device: &ash::Device,
+    dst_set: vk::DescriptorSet,
+    description: &BindlessTextureDescription,
+    index: usize,
+    texture: &Texture,
+) {
+    let mut image_info = vk::DescriptorImageInfo::default()
+        .image_layout(description.layout)
+        .image_view(texture.image_view);
+    if description.descriptor_type == vk::DescriptorType::COMBINED_IMAGE_SAMPLER {
+        image_info = image_info.sampler(texture.sampler);
+    }
+    let image_info = [image_info];
+    let image_write = vk::WriteDescriptorSet::default()
+        .dst_set(dst_set)
+        .dst_binding(description.binding)
+        .dst_array_element(index as u32)
+        .descriptor_type(description.descriptor_type)
+        .descriptor_count(1)
+        .image_info(&image_info);
+
+    let writes = [image_write];
+    unsafe { device.update_descriptor_sets(&writes, &[]) };
+}
+
+fn create_texture<S: TextureShape>(
     source_file_name: String,
-    input_image: &image::DynamicImage,
+    input_images: &[&image::DynamicImage],
     instance: &ash::Instance,
     device: &ash::Device,
     physical_device: vk::PhysicalDevice,
     physical_device_properties: vk::PhysicalDeviceProperties,
+    device_features: &DeviceFeatures,
     command_pool: vk::CommandPool,
     graphics_queue: vk::Queue,
 ) -> anyhow::Result<Texture> {
-    let (texture_image, texture_image_memory, mip_levels) = create_texture_image(
-        input_image,
+    let (texture_image, texture_image_memory, mip_levels, array_layers) = create_texture_image::<S>(
+        input_images,
         instance,
         device,
         physical_device,
@@ -2064,10 +3238,18 @@ fn create_texture(
         texture_image,
         platform::TEXTURE_IMAGE_FORMAT,
         vk::ImageAspectFlags::COLOR,
+        S::VIEW_TYPE,
         mip_levels,
+        array_layers,
     )?;
 
-    let texture_sampler = create_texture_sampler(device, physical_device_properties)?;
+    let texture_sampler = create_sampler(
+        device,
+        physical_device_properties,
+        device_features,
+        vk::Filter::LINEAR,
+        vk::SamplerAddressMode::REPEAT,
+    )?;
 
     Ok(Texture {
         source_file_name,
@@ -2076,24 +3258,38 @@ fn create_texture(
         mip_levels,
         image_view: texture_image_view,
         sampler: texture_sampler,
+        view_type: S::VIEW_TYPE,
     })
 }
 
-fn create_texture_image(
-    image: &image::DynamicImage,
+/// returns the image, its memory, mip levels, and array layers
+fn create_texture_image<S: TextureShape>(
+    images: &[&image::DynamicImage],
     instance: &ash::Instance,
     device: &ash::Device,
     physical_device: vk::PhysicalDevice,
     command_pool: vk::CommandPool,
     graphics_queue: vk::Queue,
-) -> Result<(vk::Image, vk::DeviceMemory, u32), anyhow::Error> {
-    let bytes = image.to_rgba8().into_raw();
+) -> Result<(vk::Image, vk::DeviceMemory, u32, u32), anyhow::Error> {
+    // layers (or depth slices) are tightly packed one after the other
+    let mut bytes = vec![];
+    for image in images {
+        bytes.extend(image.to_rgba8().into_raw());
+    }
+    let (width, height) = (images[0].width(), images[0].height());
+    let image_count = images.len() as u32;
     debug_assert!(
-        bytes.len() == (image.width() * image.height() * 4) as usize,
+        bytes.len() == (width * height * image_count * 4) as usize,
         "expected rgba bytes size"
     );
 
-    let mip_levels = image.width().max(image.height()).ilog2() + 1;
+    let (depth, array_layers) = if S::DEPTH_SLICES {
+        (image_count, 1)
+    } else {
+        (1, image_count)
+    };
+
+    let mip_levels = width.max(height).max(depth).ilog2() + 1;
 
     let buffer_size = bytes.len() as u64;
     let (staging_buffer, staging_buffer_memory) = create_memory_buffer(
@@ -2105,13 +3301,17 @@ fn create_texture_image(
         vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
     )?;
 
-    unsafe { write_to_gpu_buffer(device, staging_buffer_memory, &bytes)? };
+    write_to_gpu_buffer(device, staging_buffer_memory, &bytes)?;
 
-    let extent = vk::Extent2D::default()
-        .width(image.width())
-        .height(image.height());
+    let extent = vk::Extent3D::default()
+        .width(width)
+        .height(height)
+        .depth(depth);
     let image_options = ImageOptions {
+        image_type: S::IMAGE_TYPE,
+        flags: S::CREATE_FLAGS,
         extent,
+        array_layers,
         format: platform::TEXTURE_IMAGE_FORMAT,
         tiling: vk::ImageTiling::OPTIMAL,
         usage: vk::ImageUsageFlags::TRANSFER_DST
@@ -2133,6 +3333,7 @@ fn create_texture_image(
         vk::ImageLayout::UNDEFINED,
         vk::ImageLayout::TRANSFER_DST_OPTIMAL,
         mip_levels,
+        array_layers,
     )?;
 
     copy_buffer_to_image(
@@ -2142,6 +3343,7 @@ fn create_texture_image(
         staging_buffer,
         vk_image,
         extent,
+        array_layers,
     )?;
 
     generate_mipmaps(
@@ -2149,8 +3351,9 @@ fn create_texture_image(
         command_pool,
         graphics_queue,
         vk_image,
-        (extent.width as i32, extent.height as i32),
+        (width as i32, height as i32, depth as i32),
         mip_levels,
+        array_layers,
         instance,
         physical_device,
         platform::TEXTURE_IMAGE_FORMAT,
@@ -2161,11 +3364,14 @@ fn create_texture_image(
         device.free_memory(staging_buffer_memory, None);
     }
 
-    Ok((vk_image, image_memory, mip_levels))
+    Ok((vk_image, image_memory, mip_levels, array_layers))
 }
 
 struct ImageOptions {
-    extent: vk::Extent2D,
+    image_type: vk::ImageType,
+    flags: vk::ImageCreateFlags,
+    extent: vk::Extent3D,
+    array_layers: u32,
     format: vk::Format,
     tiling: vk::ImageTiling,
     usage: vk::ImageUsageFlags,
@@ -2181,10 +3387,11 @@ fn create_vk_image(
     options: ImageOptions,
 ) -> Result<(vk::Image, vk::DeviceMemory), anyhow::Error> {
     let image_create_info = vk::ImageCreateInfo::default()
-        .image_type(vk::ImageType::TYPE_2D)
-        .extent(options.extent.into())
+        .flags(options.flags)
+        .image_type(options.image_type)
+        .extent(options.extent)
         .mip_levels(options.mip_levels)
-        .array_layers(1)
+        .array_layers(options.array_layers)
         .format(options.format)
         .tiling(options.tiling)
         .initial_layout(vk::ImageLayout::UNDEFINED)
@@ -2259,6 +3466,7 @@ fn transition_image_layout(
     old_layout: vk::ImageLayout,
     new_layout: vk::ImageLayout,
     mip_levels: u32,
+    layer_count: u32,
 ) -> Result<(), anyhow::Error> {
     let command_buffer = begin_single_time_commands(device, command_pool)?;
 
@@ -2267,7 +3475,7 @@ fn transition_image_layout(
         .base_mip_level(0)
         .level_count(mip_levels)
         .base_array_layer(0)
-        .layer_count(1);
+        .layer_count(layer_count);
     let mut barrier = vk::ImageMemoryBarrier::default()
         .old_layout(old_layout)
         .new_layout(new_layout)
@@ -2341,7 +3549,8 @@ fn copy_buffer_to_image(
     graphics_queue: vk::Queue,
     buffer: vk::Buffer,
     image: vk::Image,
-    extent: vk::Extent2D,
+    extent: vk::Extent3D,
+    layer_count: u32,
 ) -> Result<(), anyhow::Error> {
     let command_buffer = begin_single_time_commands(device, command_pool)?;
 
@@ -2349,7 +3558,7 @@ fn copy_buffer_to_image(
         .aspect_mask(vk::ImageAspectFlags::COLOR)
         .mip_level(0)
         .base_array_layer(0)
-        .layer_count(1);
+        .layer_count(layer_count);
 
     let region = vk::BufferImageCopy::default()
         .buffer_offset(0)
@@ -2357,7 +3566,7 @@ fn copy_buffer_to_image(
         .buffer_image_height(0)
         .image_subresource(image_subresource)
         .image_offset(vk::Offset3D::default())
-        .image_extent(extent.into());
+        .image_extent(extent);
 
     unsafe {
         let regions = [region];
@@ -2380,7 +3589,9 @@ fn create_image_view(
     image: vk::Image,
     format: vk::Format,
     aspect_mask: vk::ImageAspectFlags,
+    view_type: vk::ImageViewType,
     mip_levels: u32,
+    layer_count: u32,
 ) -> Result<vk::ImageView, anyhow::Error> {
     let components = vk::ComponentMapping::default()
         // NOTE these are the default
@@ -2394,11 +3605,11 @@ fn create_image_view(
         .base_mip_level(0)
         .level_count(mip_levels)
         .base_array_layer(0)
-        .layer_count(1);
+        .layer_count(layer_count);
 
     let create_info = vk::ImageViewCreateInfo::default()
         .image(image)
-        .view_type(vk::ImageViewType::TYPE_2D)
+        .view_type(view_type)
         .format(format)
         .components(components)
         .subresource_range(subresource_range);
@@ -2408,18 +3619,27 @@ fn create_image_view(
     Ok(image_view)
 }
 
-fn create_texture_sampler(
+fn create_sampler(
     device: &ash::Device,
     physical_device_properties: vk::PhysicalDeviceProperties,
+    device_features: &DeviceFeatures,
+    filter: vk::Filter,
+    address_mode: vk::SamplerAddressMode,
 ) -> Result<vk::Sampler, anyhow::Error> {
-    let max_anisotropy = physical_device_properties.limits.max_sampler_anisotropy;
+    // anisotropic filtering has no effect on nearest samplers
+    let anisotropy_enable = device_features.sampler_anisotropy && filter == vk::Filter::LINEAR;
+    let max_anisotropy = if anisotropy_enable {
+        physical_device_properties.limits.max_sampler_anisotropy
+    } else {
+        1.0
+    };
     let create_info = vk::SamplerCreateInfo::default()
-        .mag_filter(vk::Filter::LINEAR)
-        .min_filter(vk::Filter::LINEAR)
-        .address_mode_u(vk::SamplerAddressMode::REPEAT)
-        .address_mode_v(vk::SamplerAddressMode::REPEAT)
-        .address_mode_w(vk::SamplerAddressMode::REPEAT)
-        .anisotropy_enable(true)
+        .mag_filter(filter)
+        .min_filter(filter)
+        .address_mode_u(address_mode)
+        .address_mode_v(address_mode)
+        .address_mode_w(address_mode)
+        .anisotropy_enable(anisotropy_enable)
         .max_anisotropy(max_anisotropy)
         .border_color(vk::BorderColor::INT_OPAQUE_BLACK)
         .unnormalized_coordinates(false)
@@ -2449,7 +3669,10 @@ fn create_depth_buffer_image(
     let mip_levels = 1;
 
     let image_options = ImageOptions {
-        extent: swapchain_extent,
+        image_type: vk::ImageType::TYPE_2D,
+        flags: vk::ImageCreateFlags::empty(),
+        extent: swapchain_extent.into(),
+        array_layers: 1,
         format: depth_format,
         tiling: vk::ImageTiling::OPTIMAL,
         usage: vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT,
@@ -2466,7 +3689,9 @@ fn create_depth_buffer_image(
         depth_image,
         depth_format,
         vk::ImageAspectFlags::DEPTH,
+        vk::ImageViewType::TYPE_2D,
         mip_levels,
+        1,
     )?;
 
     transition_image_layout(
@@ -2478,6 +3703,7 @@ fn create_depth_buffer_image(
         vk::ImageLayout::UNDEFINED,
         vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
         mip_levels,
+        1,
     )?;
 
     Ok((depth_image, depth_image_memory, depth_image_view))
@@ -2536,8 +3762,9 @@ fn generate_mipmaps(
     command_pool: vk::CommandPool,
     graphics_queue: vk::Queue,
     image: vk::Image,
-    tex_extent: (i32, i32),
+    tex_extent: (i32, i32, i32),
     mip_levels: u32,
+    layer_count: u32,
     instance: &ash::Instance,
     physical_device: vk::PhysicalDevice,
     format: vk::Format,
@@ -2557,7 +3784,7 @@ fn generate_mipmaps(
     let subresource_range = vk::ImageSubresourceRange::default()
         .aspect_mask(vk::ImageAspectFlags::COLOR)
         .base_array_layer(0)
-        .layer_count(1)
+        .layer_count(layer_count)
         .level_count(1);
     let mut barrier = vk::ImageMemoryBarrier::default()
         .image(image)
@@ -2568,6 +3795,7 @@ fn generate_mipmaps(
     // record blit commands
     let mut mip_width = tex_extent.0;
     let mut mip_height = tex_extent.1;
+    let mut mip_depth = tex_extent.2;
     for i in 1..mip_levels {
         barrier.subresource_range.base_mip_level = i - 1;
         barrier.old_layout = vk::ImageLayout::TRANSFER_DST_OPTIMAL;
@@ -2592,16 +3820,19 @@ fn generate_mipmaps(
             .aspect_mask(vk::ImageAspectFlags::COLOR)
             .mip_level(i - 1)
             .base_array_layer(0)
-            .layer_count(1);
+            .layer_count(layer_count);
         let dst_subresource = vk::ImageSubresourceLayers::default()
             .aspect_mask(vk::ImageAspectFlags::COLOR)
             .mip_level(i)
             .base_array_layer(0)
-            .layer_count(1);
+            .layer_count(layer_count);
         let blit = vk::ImageBlit::default()
             .src_offsets([
                 vk::Offset3D::default(),
-                vk::Offset3D::default().x(mip_width).y(mip_height).z(1),
+                vk::Offset3D::default()
+                    .x(mip_width)
+                    .y(mip_height)
+                    .z(mip_depth),
             ])
             .src_subresource(src_subresource)
             .dst_offsets([
@@ -2609,7 +3840,7 @@ fn generate_mipmaps(
                 vk::Offset3D::default()
                     .x(if mip_width > 1 { mip_width / 2 } else { 1 })
                     .y(if mip_height > 1 { mip_height / 2 } else { 1 })
-                    .z(1),
+                    .z(if mip_depth > 1 { mip_depth / 2 } else { 1 }),
             ])
             .dst_subresource(dst_subresource);
 
@@ -2650,6 +3881,10 @@ fn generate_mipmaps(
         if mip_height > 1 {
             mip_height /= 2;
         }
+
+        if mip_depth > 1 {
+            mip_depth /= 2;
+        }
     }
 
     barrier.subresource_range.base_mip_level = mip_levels - 1;
@@ -2718,7 +3953,10 @@ fn create_color_image(
 ) -> Result<(vk::Image, vk::DeviceMemory, vk::ImageView), anyhow::Error> {
     let mip_levels = 1;
     let image_options = ImageOptions {
-        extent: swapchain_extent,
+        image_type: vk::ImageType::TYPE_2D,
+        flags: vk::ImageCreateFlags::empty(),
+        extent: swapchain_extent.into(),
+        array_layers: 1,
         format: color_format,
         tiling: vk::ImageTiling::OPTIMAL,
         usage: vk::ImageUsageFlags::TRANSIENT_ATTACHMENT | vk::ImageUsageFlags::COLOR_ATTACHMENT,
@@ -2735,7 +3973,9 @@ fn create_color_image(
         color_image,
         color_format,
         vk::ImageAspectFlags::COLOR,
+        vk::ImageViewType::TYPE_2D,
         mip_levels,
+        1,
     )?;
 
     Ok((color_image, color_image_memory, color_image_view))
@@ -2743,7 +3983,8 @@ fn create_color_image(
 
 struct ShaderPipelineLayout {
     vertex_shader: PrecompiledShader,
-    fragment_shader: PrecompiledShader,
+    fragment_shader: Option<PrecompiledShader>,
+    specialization_constants: SpecializationConstants,
 
     // NOTE the renderer is expected to clean up these fields correctly
     // they need special handling during hot reload
@@ -2755,30 +3996,42 @@ impl ShaderPipelineLayout {
     #[cfg(debug_assertions)]
     fn create_from_atlas(
         device: &ash::Device,
+        device_features: &DeviceFeatures,
         shader: &dyn ShaderAtlasEntry,
     ) -> Result<Self, anyhow::Error> {
         let shaders::ReflectedShader {
             vertex_shader,
             fragment_shader,
             reflection_json,
-        } = shaders::dev_compile_slang_shaders(shader.source_file_name())?;
+            ..
+        } = shaders::dev_compile_slang_shaders(shader.source_file_name(), shader.variant())?;
+
+        let Some(vertex_shader) = vertex_shader else {
+            anyhow::bail!("missing vertex entry point");
+        };
 
         let vertex_shader = PrecompiledShader {
             spv_bytes: vertex_shader.spv_bytes()?,
             entry_point_name: vertex_shader.entry_point_name,
         };
 
-        let fragment_shader = PrecompiledShader {
-            spv_bytes: fragment_shader.spv_bytes()?,
-            entry_point_name: fragment_shader.entry_point_name,
+        let fragment_shader = match fragment_shader {
+            Some(fragment_shader) => Some(PrecompiledShader {
+                spv_bytes: fragment_shader.spv_bytes()?,
+                entry_point_name: fragment_shader.entry_point_name,
+            }),
+            None => None,
         };
 
+        device_features.check_shaders(&vertex_shader, fragment_shader.as_ref())?;
+
         let (pipeline_layout, descriptor_set_layouts) =
             unsafe { reflection_json.pipeline_layout.vk_create(device)? };
 
         Ok(ShaderPipelineLayout {
             vertex_shader,
             fragment_shader,
+            specialization_constants: shader.specialization_constants(),
             pipeline_layout,
             descriptor_set_layouts,
         })
@@ -2787,16 +4040,86 @@ impl ShaderPipelineLayout {
     #[cfg(not(debug_assertions))]
     fn create_from_atlas(
         device: &ash::Device,
+        device_features: &DeviceFeatures,
         shader: &dyn ShaderAtlasEntry,
     ) -> Result<Self, anyhow::Error> {
         let precompiled = shader.precompiled_shaders();
 
+        device_features.check_shaders(&precompiled.vert, precompiled.frag.as_ref())?;
+
         let (pipeline_layout, descriptor_set_layouts) =
             unsafe { shader.pipeline_layout().vk_create(device)? };
 
         Ok(ShaderPipelineLayout {
             vertex_shader: precompiled.vert,
             fragment_shader: precompiled.frag,
+            specialization_constants: shader.specialization_constants(),
+            pipeline_layout,
+            descriptor_set_layouts,
+        })
+    }
+}
+
+struct ComputeShaderPipelineLayout {
+    compute_shader: PrecompiledShader,
+    specialization_constants: SpecializationConstants,
+
+    // NOTE the renderer is expected to clean up these fields correctly
+    pipeline_layout: ash::vk::PipelineLayout,
+    descriptor_set_layouts: Vec<ash::vk::DescriptorSetLayout>,
+}
+
+impl ComputeShaderPipelineLayout {
+    #[cfg(debug_assertions)]
+    fn create_from_atlas(
+        device: &ash::Device,
+        device_features: &DeviceFeatures,
+        shader: &dyn ComputeShaderAtlasEntry,
+    ) -> Result<Self, anyhow::Error> {
+        let shaders::ReflectedShader {
+            compute_shader,
+            reflection_json,
+            ..
+        } = shaders::dev_compile_slang_shaders(shader.source_file_name(), shader.variant())?;
+
+        let Some(compute_shader) = compute_shader else {
+            anyhow::bail!("missing compute entry point");
+        };
+
+        let compute_shader = PrecompiledShader {
+            spv_bytes: compute_shader.spv_bytes()?,
+            entry_point_name: compute_shader.entry_point_name,
+        };
+
+        device_features.check_shaders(&compute_shader, None)?;
+
+        let (pipeline_layout, descriptor_set_layouts) =
+            unsafe { reflection_json.pipeline_layout.vk_create(device)? };
+
+        Ok(ComputeShaderPipelineLayout {
+            compute_shader,
+            specialization_constants: shader.specialization_constants(),
+            pipeline_layout,
+            descriptor_set_layouts,
+        })
+    }
+
+    #[cfg(not(debug_assertions))]
+    fn create_from_atlas(
+        device: &ash::Device,
+        device_features: &DeviceFeatures,
+        shader: &dyn ComputeShaderAtlasEntry,
+    ) -> Result<Self, anyhow::Error> {
+        let compute_shader = shader.precompiled_shader();
+
+        device_features.check_shaders(&compute_shader, None)?;
+
+        let (pipeline_layout, descriptor_set_layouts) =
+            unsafe { shader.pipeline_layout().vk_create(device)? };
+
+        Ok(ComputeShaderPipelineLayout {
+            compute_shader,
+            specialization_constants: shader.specialization_constants(),
             pipeline_layout,
             descriptor_set_layouts,
         })
@@ -2809,7 +4132,34 @@ impl shaders::json::ReflectedDescriptorSetLayout {
         device: &ash::Device,
     ) -> Result<vk::DescriptorSetLayout, vk::Result> {
         let binding_ranges: Vec<_> = self.binding_ranges.iter().map(|b| b.to_vk()).collect();
-        let create_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&binding_ranges);
+
+        // unbounded arrays are partially bound, and updated as textures are added
+        let binding_flags: Vec<_> = self
+            .binding_ranges
+            .iter()
+            .map(|b| {
+                if b.unbounded {
+                    vk::DescriptorBindingFlags::PARTIALLY_BOUND
+                        | vk::DescriptorBindingFlags::UPDATE_AFTER_BIND
+                        | vk::DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING
+                } else {
+                    vk::DescriptorBindingFlags::empty()
+                }
+            })
+            .collect();
+        let mut binding_flags_info =
+            vk::DescriptorSetLayoutBindingFlagsCreateInfo::default().binding_flags(&binding_flags);
+
+        let flags = if self.binding_ranges.iter().any(|b| b.unbounded) {
+            vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL
+        } else {
+            vk::DescriptorSetLayoutCreateFlags::empty()
+        };
+
+        let create_info = vk::DescriptorSetLayoutCreateInfo::default()
+            .flags(flags)
+            .bindings(&binding_ranges)
+            .push_next(&mut binding_flags_info);
 
         unsafe { device.create_descriptor_set_layout(&create_info, None) }
     }
@@ -2820,7 +4170,11 @@ impl shaders::json::ReflectedDescriptorSetLayoutBinding {
         vk::DescriptorSetLayoutBinding::default()
             .stage_flags(self.stage_flags.to_vk())
             .binding(self.binding)
-            .descriptor_count(self.descriptor_count)
+            .descriptor_count(if self.unbounded {
+                BINDLESS_TEXTURE_CAPACITY
+            } else {
+                self.descriptor_count
+            })
             .descriptor_type(self.descriptor_type.to_vk())
     }
 }
@@ -2832,6 +4186,8 @@ impl shaders::json::ReflectedBindingType {
             Self::Texture => vk::DescriptorType::SAMPLED_IMAGE,
             Self::ConstantBuffer => vk::DescriptorType::UNIFORM_BUFFER,
             Self::CombinedTextureSampler => vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+            Self::StorageBuffer => vk::DescriptorType::STORAGE_BUFFER,
+            Self::StorageTexelBuffer => vk::DescriptorType::STORAGE_TEXEL_BUFFER,
         }
     }
 }
@@ -2847,11 +4203,7 @@ impl shaders::json::ReflectedPipelineLayout {
             descriptor_set_layouts.push(created_set_layout);
         }
 
-        let push_constant_ranges: Vec<_> = self
-            .push_constant_ranges
-            .iter()
-            .map(|r| r.to_vk())
-            .collect();
+        let push_constant_ranges = self.push_constant_ranges_vk();
 
         let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
             .set_layouts(&descriptor_set_layouts)
@@ -2862,13 +4214,39 @@ impl shaders::json::ReflectedPipelineLayout {
 
         Ok((pipeline_layout, descriptor_set_layouts))
     }
+
+    /// slang places each entry point's push constants at offset 0,
+    /// so overlapping ranges are merged into one range for all of their stages;
+    /// otherwise each push would need every stage of the ranges it overlaps
+    fn push_constant_ranges_vk(&self) -> Vec<vk::PushConstantRange> {
+        let mut ranges: Vec<_> = self
+            .push_constant_ranges
+            .iter()
+            .map(|r| r.to_vk())
+            .collect();
+        ranges.sort_by_key(|range| range.offset);
+
+        let mut merged_ranges: Vec<vk::PushConstantRange> = vec![];
+        for range in ranges {
+            match merged_ranges.last_mut() {
+                Some(merged) if range.offset < merged.offset + merged.size => {
+                    let end = (merged.offset + merged.size).max(range.offset + range.size);
+                    merged.size = end - merged.offset;
+                    merged.stage_flags |= range.stage_flags;
+                }
+                _ => merged_ranges.push(range),
+            }
+        }
+
+        merged_ranges
+    }
 }
 
 impl shaders::json::ReflectedPushConstantRange {
     fn to_vk(&self) -> vk::PushConstantRange {
         vk::PushConstantRange::default()
             .stage_flags(self.stage_flags.to_vk())
-            .offset(self.size)
+            .offset(self.offset)
             .size(self.size)
     }
 }
@@ -2888,14 +4266,39 @@ impl shaders::json::ReflectedStageFlags {
 pub struct Gpu<'frame> {
     current_frame: usize,
     uniform_buffers: &'frame mut UniformBufferStorage,
+    pipelines: &'frame mut PipelineStorage,
 }
 
 impl<'frame> Gpu<'frame> {
-    pub fn write_uniform<T>(&mut self, uniform_buffer: &mut UniformBufferHandle<T>, data: T) {
-        let mapped_mem = self
+    pub fn write_uniform<T: UniformData>(
+        &mut self,
+        uniform_buffer: &mut UniformBufferHandle<T>,
+        data: T,
+    ) {
+        let mapped_bytes = self
             .uniform_buffers
-            .get_mapped_mem_for_frame(uniform_buffer, self.current_frame);
+            .get_mapped_bytes_for_frame(uniform_buffer, self.current_frame);
+
+        mapped_bytes.copy_from_slice(bytemuck::bytes_of(&data));
+    }
 
-        *mapped_mem = data;
+    /// sets the push constants recorded with the pipeline's draw,
+    /// for this frame and later ones until they're set again; they start zeroed
+    pub fn push_constants<P: PushConstantData>(
+        &mut self,
+        pipeline: &PipelineHandle<P>,
+        push_constants: P,
+    ) {
+        let renderer_pipeline = self.pipelines.get_mut(pipeline);
+        renderer_pipeline.push_constants = bytemuck::bytes_of(&push_constants).to_vec();
     }
 }
+
+/// the end of the last push constant range, in bytes
+fn push_constant_size(push_constant_ranges: &[vk::PushConstantRange]) -> usize {
+    push_constant_ranges
+        .iter()
+        .map(|range| (range.offset + range.size) as usize)
+        .max()
+        .unwrap_or_default()
+}
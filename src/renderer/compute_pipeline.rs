@@ -0,0 +1,116 @@
+use std::marker::PhantomData;
+
+use ash::vk;
+
+use crate::shaders::atlas::ComputeShaderAtlasEntry;
+
+use super::ComputeShaderPipelineLayout;
+use super::RawDescriptorHandle;
+
+/// P is the shader's generated PushConstants type, or () for a shader without push constants
+#[derive(Debug)]
+pub struct ComputePipelineHandle<P = ()> {
+    index: usize,
+    push_constants: PhantomData<P>,
+}
+
+pub(super) struct ComputePipelineStorage {
+    pipelines: Vec<Option<RendererComputePipeline>>,
+    /// dispatches waiting to be recorded at the start of the next frame
+    pending_dispatches: Vec<PendingDispatch>,
+}
+
+pub(super) struct PendingDispatch {
+    pub handle: ComputePipelineHandle,
+    pub group_counts: [u32; 3],
+    /// the push constants recorded with this dispatch, if the shader has any
+    pub push_constants: Option<Vec<u8>>,
+}
+
+impl ComputePipelineStorage {
+    pub fn new() -> Self {
+        Self {
+            pipelines: Default::default(),
+            pending_dispatches: Default::default(),
+        }
+    }
+
+    pub fn add<P>(&mut self, pipeline: RendererComputePipeline) -> ComputePipelineHandle<P> {
+        let index = self.pipelines.len();
+        let handle = ComputePipelineHandle {
+            index,
+            push_constants: PhantomData,
+        };
+        self.pipelines.push(Some(pipeline));
+
+        handle
+    }
+
+    pub fn get<P>(&self, handle: &ComputePipelineHandle<P>) -> &RendererComputePipeline {
+        self.pipelines[handle.index].as_ref().unwrap()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RendererComputePipeline> {
+        self.pipelines.iter().flatten()
+    }
+
+    pub fn queue_dispatch<P>(
+        &mut self,
+        handle: &ComputePipelineHandle<P>,
+        group_counts: [u32; 3],
+        push_constants: Option<Vec<u8>>,
+    ) {
+        let handle = ComputePipelineHandle {
+            index: handle.index,
+            push_constants: PhantomData,
+        };
+
+        self.pending_dispatches.push(PendingDispatch {
+            handle,
+            group_counts,
+            push_constants,
+        });
+    }
+
+    pub fn take_pending_dispatches(&mut self) -> Vec<PendingDispatch> {
+        std::mem::take(&mut self.pending_dispatches)
+    }
+
+    pub fn take<P>(&mut self, handle: ComputePipelineHandle<P>) -> RendererComputePipeline {
+        self.pending_dispatches
+            .retain(|dispatch| dispatch.handle.index != handle.index);
+
+        self.pipelines[handle.index].take().unwrap()
+    }
+
+    pub fn take_all(&mut self) -> Vec<RendererComputePipeline> {
+        self.pending_dispatches.clear();
+
+        self.pipelines
+            .iter_mut()
+            .filter_map(|option| option.take())
+            .collect()
+    }
+}
+
+pub(super) struct RendererComputePipeline {
+    pub layout: ComputeShaderPipelineLayout,
+    pub pipeline: vk::Pipeline,
+
+    pub descriptor_pool: vk::DescriptorPool,
+    pub descriptor_sets: Vec<vk::DescriptorSet>,
+
+    /// the layout's push constant ranges, with overlapping ranges merged
+    pub push_constant_ranges: Vec<vk::PushConstantRange>,
+
+    pub shader: Box<dyn ComputeShaderAtlasEntry>,
+}
+
+/// the generic arguments for creating a compute pipeline
+/// P is the generated PushConstants type, or () for shaders without push constants
+pub struct ComputePipelineConfig<P = ()> {
+    pub shader: Box<dyn ComputeShaderAtlasEntry>,
+    /// resource handles by descriptor set, in binding order
+    pub descriptor_set_handles: Vec<Vec<RawDescriptorHandle>>,
+    pub push_constants: PhantomData<P>,
+}
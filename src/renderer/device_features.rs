@@ -0,0 +1,190 @@
+use ash::vk;
+
+use crate::shaders::atlas::PrecompiledShader;
+
+/// optional device features used by shader types and samplers,
+/// enabled on the logical device when the physical device supports them
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct DeviceFeatures {
+    /// anisotropic filtering for linear samplers
+    pub sampler_anisotropy: bool,
+    /// double
+    pub shader_float64: bool,
+    /// int64_t and uint64_t (also used by shader println)
+    pub shader_int64: bool,
+    /// half arithmetic
+    pub shader_float16: bool,
+    /// half in storage buffers
+    pub storage_buffer16_bit_access: bool,
+    /// half in uniform buffers
+    pub uniform_and_storage_buffer16_bit_access: bool,
+    /// half in push constants
+    pub storage_push_constant16: bool,
+    /// half vertex inputs and varyings
+    pub storage_input_output16: bool,
+    /// unbounded texture arrays (ie bindless textures)
+    pub descriptor_indexing: bool,
+    /// pointer fields (also used by shader println)
+    pub buffer_device_address: bool,
+}
+
+impl DeviceFeatures {
+    pub fn query(instance: &ash::Instance, physical_device: vk::PhysicalDevice) -> Self {
+        let mut float16_int8_features = vk::PhysicalDeviceShaderFloat16Int8Features::default();
+        let mut sixteen_bit_storage_features = vk::PhysicalDevice16BitStorageFeatures::default();
+        let mut descriptor_indexing_features =
+            vk::PhysicalDeviceDescriptorIndexingFeatures::default();
+        let mut buffer_device_address_features =
+            vk::PhysicalDeviceBufferDeviceAddressFeatures::default();
+        let mut features = vk::PhysicalDeviceFeatures2::default()
+            .push_next(&mut float16_int8_features)
+            .push_next(&mut sixteen_bit_storage_features)
+            .push_next(&mut descriptor_indexing_features)
+            .push_next(&mut buffer_device_address_features);
+        unsafe { instance.get_physical_device_features2(physical_device, &mut features) };
+        let core_features = features.features;
+
+        Self {
+            sampler_anisotropy: core_features.sampler_anisotropy == vk::TRUE,
+            shader_float64: core_features.shader_float64 == vk::TRUE,
+            shader_int64: core_features.shader_int64 == vk::TRUE,
+            shader_float16: float16_int8_features.shader_float16 == vk::TRUE,
+            storage_buffer16_bit_access: sixteen_bit_storage_features.storage_buffer16_bit_access
+                == vk::TRUE,
+            uniform_and_storage_buffer16_bit_access: sixteen_bit_storage_features
+                .uniform_and_storage_buffer16_bit_access
+                == vk::TRUE,
+            storage_push_constant16: sixteen_bit_storage_features.storage_push_constant16
+                == vk::TRUE,
+            storage_input_output16: sixteen_bit_storage_features.storage_input_output16 == vk::TRUE,
+            // all of the features used by bindless texture arrays (see create_logical_device)
+            descriptor_indexing: [
+                descriptor_indexing_features.runtime_descriptor_array,
+                descriptor_indexing_features.shader_sampled_image_array_non_uniform_indexing,
+                descriptor_indexing_features.descriptor_binding_partially_bound,
+                descriptor_indexing_features.descriptor_binding_sampled_image_update_after_bind,
+                descriptor_indexing_features.descriptor_binding_update_unused_while_pending,
+            ]
+            .into_iter()
+            .all(|supported| supported == vk::TRUE),
+            buffer_device_address: buffer_device_address_features.buffer_device_address == vk::TRUE,
+        }
+    }
+
+    /// fails if a shader's spirv declares a capability for a feature the device doesn't support
+    pub fn check_shaders(
+        &self,
+        shader: &PrecompiledShader,
+        other_shader: Option<&PrecompiledShader>,
+    ) -> anyhow::Result<()> {
+        for shader in std::iter::once(shader).chain(other_shader) {
+            self.check_shader(shader)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_shader(&self, shader: &PrecompiledShader) -> anyhow::Result<()> {
+        for capability in spirv_capabilities(&shader.spv_bytes) {
+            let (feature, supported) = match capability {
+                SPV_CAPABILITY_FLOAT64 => ("shaderFloat64", self.shader_float64),
+                SPV_CAPABILITY_INT64 => ("shaderInt64", self.shader_int64),
+                SPV_CAPABILITY_FLOAT16 => ("shaderFloat16", self.shader_float16),
+                SPV_CAPABILITY_STORAGE_BUFFER_16_BIT_ACCESS => {
+                    ("storageBuffer16BitAccess", self.storage_buffer16_bit_access)
+                }
+                SPV_CAPABILITY_UNIFORM_AND_STORAGE_BUFFER_16_BIT_ACCESS => (
+                    "uniformAndStorageBuffer16BitAccess",
+                    self.uniform_and_storage_buffer16_bit_access,
+                ),
+                SPV_CAPABILITY_STORAGE_PUSH_CONSTANT_16 => {
+                    ("storagePushConstant16", self.storage_push_constant16)
+                }
+                SPV_CAPABILITY_STORAGE_INPUT_OUTPUT_16 => {
+                    ("storageInputOutput16", self.storage_input_output16)
+                }
+                SPV_CAPABILITY_RUNTIME_DESCRIPTOR_ARRAY => {
+                    ("runtimeDescriptorArray", self.descriptor_indexing)
+                }
+                SPV_CAPABILITY_SAMPLED_IMAGE_ARRAY_NON_UNIFORM_INDEXING => (
+                    "shaderSampledImageArrayNonUniformIndexing",
+                    self.descriptor_indexing,
+                ),
+                SPV_CAPABILITY_PHYSICAL_STORAGE_BUFFER_ADDRESSES => {
+                    ("bufferDeviceAddress", self.buffer_device_address)
+                }
+                _ => continue,
+            };
+
+            if !supported {
+                anyhow::bail!(
+                    "shader entry point {:?} requires the unsupported device feature {feature}",
+                    shader.entry_point_name
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// fails if the device can't read the vertex attribute formats from a vertex buffer,
+/// ie the optional three and four component 64-bit formats
+pub(super) fn check_vertex_formats(
+    instance: &ash::Instance,
+    physical_device: vk::PhysicalDevice,
+    attribute_descriptions: &[vk::VertexInputAttributeDescription],
+) -> anyhow::Result<()> {
+    for description in attribute_descriptions {
+        let format_properties = unsafe {
+            instance.get_physical_device_format_properties(physical_device, description.format)
+        };
+        if !format_properties
+            .buffer_features
+            .contains(vk::FormatFeatureFlags::VERTEX_BUFFER)
+        {
+            anyhow::bail!(
+                "unsupported vertex format {:?} at location {}",
+                description.format,
+                description.location
+            );
+        }
+    }
+
+    Ok(())
+}
+
+// https://registry.khronos.org/SPIR-V/specs/unified1/SPIRV.html#_capability
+const SPV_CAPABILITY_FLOAT16: u32 = 9;
+const SPV_CAPABILITY_FLOAT64: u32 = 10;
+const SPV_CAPABILITY_INT64: u32 = 11;
+const SPV_CAPABILITY_STORAGE_BUFFER_16_BIT_ACCESS: u32 = 4433;
+const SPV_CAPABILITY_UNIFORM_AND_STORAGE_BUFFER_16_BIT_ACCESS: u32 = 4434;
+const SPV_CAPABILITY_STORAGE_PUSH_CONSTANT_16: u32 = 4435;
+const SPV_CAPABILITY_STORAGE_INPUT_OUTPUT_16: u32 = 4436;
+const SPV_CAPABILITY_RUNTIME_DESCRIPTOR_ARRAY: u32 = 5302;
+const SPV_CAPABILITY_SAMPLED_IMAGE_ARRAY_NON_UNIFORM_INDEXING: u32 = 5307;
+const SPV_CAPABILITY_PHYSICAL_STORAGE_BUFFER_ADDRESSES: u32 = 5347;
+
+const SPV_HEADER_WORDS: usize = 5;
+const SPV_OP_CAPABILITY: u32 = 17;
+
+/// the operands of the OpCapability instructions at the start of a spirv module
+fn spirv_capabilities(spv_bytes: &[u32]) -> impl Iterator<Item = u32> + '_ {
+    let mut words = spv_bytes.get(SPV_HEADER_WORDS..).unwrap_or_default();
+
+    std::iter::from_fn(move || {
+        let [instruction, capability, ..] = words else {
+            return None;
+        };
+
+        let opcode = instruction & 0xffff;
+        let word_count = (instruction >> 16) as usize;
+        if opcode != SPV_OP_CAPABILITY || word_count == 0 {
+            return None;
+        }
+
+        words = words.get(word_count..).unwrap_or_default();
+        Some(*capability)
+    })
+}
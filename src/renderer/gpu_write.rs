@@ -1,25 +1,141 @@
+use std::marker::PhantomData;
+
 use ash::vk;
+use bytemuck::{Pod, Zeroable};
+use serde::Serialize;
+
+// NOTE types written to GPU memory are bytemuck::Pod,
+// which generated structs derive along with explicit padding fields;
+// the derive fails to compile if a struct has any implicit padding
+
+/// a type declared as uniform data in a shader (ie a ParameterBlock's element type),
+/// implemented by generated code
+pub trait UniformData: Pod {}
+
+/// a type used as vertex input by a shader, either per-vertex or per-instance,
+/// implemented by generated code
+pub trait VertexData: Pod {}
+
+/// no vertex input, for shaders that draw from SV_VertexID
+impl VertexData for () {}
+
+/// a shader's generated PushConstants struct, implemented by generated code
+pub trait PushConstantData: Pod {}
+
+/// the element type of a shader's storage buffer,
+/// implemented by generated code for structs
+pub trait StorageData: Pod {}
+
+// NOTE these match the generated types for scalar and vector buffer elements,
+// ie StructuredBuffer<uint> or StructuredBuffer<float4>;
+// 3-component vectors are padded to their stride by a generated struct instead
+impl StorageData for u32 {}
+impl StorageData for i32 {}
+impl StorageData for f32 {}
+impl StorageData for u64 {}
+impl StorageData for i64 {}
+impl StorageData for f64 {}
+impl StorageData for half::f16 {}
+impl StorageData for glam::Vec2 {}
+impl StorageData for glam::Vec4 {}
+impl StorageData for glam::IVec2 {}
+impl StorageData for glam::IVec4 {}
+impl StorageData for glam::UVec2 {}
+impl StorageData for glam::UVec4 {}
+impl StorageData for glam::I64Vec2 {}
+impl StorageData for glam::I64Vec4 {}
+impl StorageData for glam::U64Vec2 {}
+impl StorageData for glam::U64Vec4 {}
+impl StorageData for glam::DVec2 {}
+impl StorageData for glam::DVec4 {}
+impl StorageData for [half::f16; 2] {}
+impl StorageData for [half::f16; 4] {}
+
+/// A vec2 padded to 16 bytes, as a std140 matrix column (or row)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Pod, Zeroable)]
+#[repr(C, align(16))]
+pub struct PaddedVec2 {
+    pub vec: glam::Vec2,
+    #[serde(skip)]
+    _padding: [f32; 2],
+}
+
+impl From<glam::Vec2> for PaddedVec2 {
+    fn from(vec: glam::Vec2) -> Self {
+        Self {
+            vec,
+            _padding: Default::default(),
+        }
+    }
+}
 
-/// A marker for someday-generated types that get written to GPU memory
+/// A buffer device address, for a slang pointer field (ie `T*`)
 ///
-/// An implementing struct must be repr(C, align(16))
-/// and have its fields in descending size/alignment order
-pub trait GPUWrite {}
+/// This comes from Renderer::storage_buffer_address,
+/// and is only valid while the buffer is alive
+#[derive(Serialize)]
+#[repr(transparent)]
+pub struct GpuPtr<T> {
+    address: vk::DeviceAddress,
+    #[serde(skip)]
+    _phantom_data: PhantomData<T>,
+}
+
+impl<T> GpuPtr<T> {
+    pub(super) fn from_address(address: vk::DeviceAddress) -> Self {
+        Self {
+            address,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// a pointer the shader must check before following, ie the end of a linked list
+    pub fn null() -> Self {
+        Self::from_address(0)
+    }
+
+    pub fn address(&self) -> vk::DeviceAddress {
+        self.address
+    }
+}
 
-impl GPUWrite for u8 {} // image bytes
-impl GPUWrite for u32 {} // index buffer
+// NOTE these are implemented by hand to avoid requiring them for T
+impl<T> Clone for GpuPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GpuPtr<T> {}
+
+// NOTE these are implemented by hand because the derives require Pod for T,
+// but this is only a u64 address; the PhantomData is zero-sized
+unsafe impl<T: 'static> Zeroable for GpuPtr<T> {}
+unsafe impl<T: 'static> Pod for GpuPtr<T> {}
+
+impl<T> std::fmt::Debug for GpuPtr<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GpuPtr({:#x})", self.address)
+    }
+}
 
-pub(super) unsafe fn write_to_gpu_buffer<T: GPUWrite>(
+/// copies the elements' bytes into host-visible buffer memory
+pub(super) fn write_to_gpu_buffer<T: Pod>(
     device: &ash::Device,
     buffer_memory: vk::DeviceMemory,
     elements: &[T],
 ) -> anyhow::Result<()> {
-    let buffer_size = std::mem::size_of_val(elements) as vk::DeviceSize;
+    let bytes: &[u8] = bytemuck::cast_slice(elements);
 
     unsafe {
-        let mapped_dst =
-            device.map_memory(buffer_memory, 0, buffer_size, Default::default())? as *mut T;
-        std::ptr::copy_nonoverlapping(elements.as_ptr(), mapped_dst, elements.len());
+        let mapped_mem = device.map_memory(
+            buffer_memory,
+            0,
+            bytes.len() as vk::DeviceSize,
+            Default::default(),
+        )?;
+        let mapped_bytes = std::slice::from_raw_parts_mut(mapped_mem as *mut u8, bytes.len());
+        mapped_bytes.copy_from_slice(bytes);
         device.unmap_memory(buffer_memory);
     };
 
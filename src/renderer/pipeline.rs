@@ -1,14 +1,22 @@
+use std::marker::PhantomData;
+
 use ash::vk;
 
 use crate::shaders::atlas::ShaderAtlasEntry;
 
 use super::ShaderPipelineLayout;
+use super::gpu_write::VertexData;
 use super::vertex_description::VertexDescription;
-use super::{RawUniformBufferHandle, TextureHandle};
+use super::{
+    RawSamplerHandle, RawStorageBufferHandle, RawTextureHandle, RawUniformBufferHandle,
+    SamplerSettings,
+};
 
+/// P is the shader's generated PushConstants type, or () for a shader without push constants
 #[derive(Debug)]
-pub struct PipelineHandle {
+pub struct PipelineHandle<P = ()> {
     index: usize,
+    push_constants: PhantomData<P>,
 }
 
 pub(super) struct PipelineStorage(Vec<Option<RendererPipeline>>);
@@ -18,25 +26,30 @@ impl PipelineStorage {
         Self(Default::default())
     }
 
-    pub fn add(&mut self, pipeline: RendererPipeline) -> PipelineHandle {
+    pub fn add<P>(&mut self, pipeline: RendererPipeline) -> PipelineHandle<P> {
         let index = self.0.len();
-        let handle = PipelineHandle { index };
+        let handle = PipelineHandle {
+            index,
+            push_constants: PhantomData,
+        };
         self.0.push(Some(pipeline));
 
         handle
     }
 
-    pub fn get(&self, handle: &PipelineHandle) -> &RendererPipeline {
+    pub fn get<P>(&self, handle: &PipelineHandle<P>) -> &RendererPipeline {
         self.0[handle.index].as_ref().unwrap()
     }
 
-    // used only for hot reload
-    #[cfg(debug_assertions)]
-    pub fn get_mut(&mut self, handle: &PipelineHandle) -> &mut RendererPipeline {
+    pub fn get_mut<P>(&mut self, handle: &PipelineHandle<P>) -> &mut RendererPipeline {
         self.0[handle.index].as_mut().unwrap()
     }
 
-    pub fn take(&mut self, handle: PipelineHandle) -> RendererPipeline {
+    pub fn iter(&self) -> impl Iterator<Item = &RendererPipeline> {
+        self.0.iter().flatten()
+    }
+
+    pub fn take<P>(&mut self, handle: PipelineHandle<P>) -> RendererPipeline {
         self.0[handle.index].take().unwrap()
     }
 
@@ -52,25 +65,59 @@ pub(super) struct RendererPipeline {
     pub layout: ShaderPipelineLayout,
     pub pipeline: vk::Pipeline,
 
-    pub vertex_buffer: vk::Buffer,
-    pub vertex_buffer_memory: vk::DeviceMemory,
+    /// None for a shader that draws from SV_VertexID
+    pub vertex_buffer: Option<(vk::Buffer, vk::DeviceMemory)>,
+    /// None for a non-indexed draw
+    pub index_buffer: Option<(vk::Buffer, vk::DeviceMemory)>,
 
-    pub index_buffer: vk::Buffer,
-    pub index_buffer_memory: vk::DeviceMemory,
+    /// the per-instance vertex buffer at binding 1, if there are any instances
+    pub instance_buffer: Option<(vk::Buffer, vk::DeviceMemory)>,
 
     pub descriptor_pool: vk::DescriptorPool,
     pub descriptor_sets: Vec<vk::DescriptorSet>,
 
     pub index_count: usize,
-    #[cfg_attr(not(debug_assertions), expect(unused))]
+    pub vertex_count: u32,
+    pub instance_count: u32,
+
+    /// the layout's push constant ranges, with overlapping ranges merged
+    pub push_constant_ranges: Vec<vk::PushConstantRange>,
+    /// the bytes of the PushConstants recorded with each draw;
+    ///   see Gpu::push_constants
+    pub push_constants: Vec<u8>,
+
     pub shader: Box<dyn ShaderAtlasEntry>,
 }
 
 /// the generic arguments for creating a pipeline
-pub struct PipelineConfig<'t, V: VertexDescription> {
+/// V is the per-vertex input, or () for shaders that draw from SV_VertexID;
+/// I is the per-instance vertex input, or () for shaders without one;
+/// P is the generated PushConstants type, or () for shaders without push constants
+pub struct PipelineConfig<V: VertexDescription, I: VertexData = (), P = ()> {
     pub shader: Box<dyn ShaderAtlasEntry>,
     pub vertices: Vec<V>,
+    /// empty for a non-indexed draw of vertex_count vertices
     pub indices: Vec<u32>,
-    pub texture_handles: Vec<&'t TextureHandle>,
-    pub uniform_buffer_handles: Vec<RawUniformBufferHandle>,
+    pub vertex_count: u32,
+    pub instances: Vec<I>,
+    /// the number of instances to draw, which can be nonzero without instance data
+    pub instance_count: u32,
+    /// resource handles by descriptor set, in binding order
+    pub descriptor_set_handles: Vec<Vec<RawDescriptorHandle>>,
+    pub push_constants: PhantomData<P>,
+}
+
+/// a resource handle for one descriptor set binding
+pub enum RawDescriptorHandle {
+    UniformBuffer(RawUniformBufferHandle),
+    StorageBuffer(RawStorageBufferHandle),
+    /// either a combined or separate texture
+    Texture(RawTextureHandle),
+    /// a combined texture, with the sampler from its Sampler attribute
+    TextureWithSampler(RawTextureHandle, SamplerSettings),
+    Sampler(RawSamplerHandle),
+    /// a separate sampler from a Sampler attribute, created by the renderer
+    SamplerSettings(SamplerSettings),
+    /// every live texture, at its bindless index
+    BindlessTextures,
 }
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use ash::vk;
+
+/// a sampler that can be shared between textures, for separate SamplerState fields
+#[derive(Debug)]
+pub struct SamplerHandle {
+    index: usize,
+}
+
+/// the settings from a slang Sampler attribute, ie '[Sampler("nearest", "clamp")]'
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SamplerSettings {
+    pub filter: vk::Filter,
+    pub address_mode: vk::SamplerAddressMode,
+}
+
+pub(super) struct SamplerStorage {
+    samplers: Vec<Option<vk::Sampler>>,
+    /// samplers for Sampler attributes, shared between pipelines until shutdown
+    samplers_by_settings: HashMap<SamplerSettings, vk::Sampler>,
+}
+
+impl SamplerStorage {
+    pub fn new() -> Self {
+        Self {
+            samplers: Default::default(),
+            samplers_by_settings: Default::default(),
+        }
+    }
+
+    pub fn add(&mut self, sampler: vk::Sampler) -> SamplerHandle {
+        let handle = SamplerHandle {
+            index: self.samplers.len(),
+        };
+        self.samplers.push(Some(sampler));
+
+        handle
+    }
+
+    pub fn add_for_settings(&mut self, settings: SamplerSettings, sampler: vk::Sampler) {
+        self.samplers_by_settings.insert(settings, sampler);
+    }
+
+    pub fn get_raw(&self, handle: &RawSamplerHandle) -> vk::Sampler {
+        self.samplers[handle.index].unwrap()
+    }
+
+    pub fn get_for_settings(&self, settings: &SamplerSettings) -> Option<vk::Sampler> {
+        self.samplers_by_settings.get(settings).copied()
+    }
+
+    pub fn take(&mut self, handle: SamplerHandle) -> vk::Sampler {
+        self.samplers[handle.index].take().unwrap()
+    }
+
+    pub fn take_all(&mut self) -> Vec<vk::Sampler> {
+        let mut samplers: Vec<vk::Sampler> = self
+            .samplers
+            .iter_mut()
+            .filter_map(|option| option.take())
+            .collect();
+        samplers.extend(
+            self.samplers_by_settings
+                .drain()
+                .map(|(_, sampler)| sampler),
+        );
+
+        samplers
+    }
+}
+
+// NOTE find a way to limit this to generated code
+//   would need to make PipelineConfig fields private
+pub struct RawSamplerHandle {
+    index: usize,
+}
+
+impl RawSamplerHandle {
+    pub fn from_handle(handle: &SamplerHandle) -> Self {
+        let index = handle.index;
+        Self { index }
+    }
+}
@@ -0,0 +1,46 @@
+use ash::vk;
+
+/// values for a shader's specialization constants,
+/// set by the generated shader's `with_*` methods
+#[derive(Debug, Clone, Default)]
+pub struct SpecializationConstants {
+    map_entries: Vec<vk::SpecializationMapEntry>,
+    data: Vec<u8>,
+}
+
+impl SpecializationConstants {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// overrides the shader's default value for the constant
+    // NOTE bools are a 4 byte VkBool32
+    pub fn set(&mut self, constant_id: u32, bytes: &[u8]) {
+        if let Some(entry) = self
+            .map_entries
+            .iter()
+            .find(|entry| entry.constant_id == constant_id)
+        {
+            let offset = entry.offset as usize;
+            self.data[offset..offset + entry.size].copy_from_slice(bytes);
+            return;
+        }
+
+        let entry = vk::SpecializationMapEntry::default()
+            .constant_id(constant_id)
+            .offset(self.data.len() as u32)
+            .size(bytes.len());
+        self.map_entries.push(entry);
+        self.data.extend_from_slice(bytes);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map_entries.is_empty()
+    }
+
+    pub(super) fn vk_info(&self) -> vk::SpecializationInfo<'_> {
+        vk::SpecializationInfo::default()
+            .map_entries(&self.map_entries)
+            .data(&self.data)
+    }
+}
@@ -0,0 +1,115 @@
+use std::ffi::c_void;
+use std::marker::PhantomData;
+
+use ash::vk;
+
+#[derive(Debug)]
+pub struct StorageBufferHandle<T> {
+    index: usize,
+    _phantom_data: PhantomData<T>,
+}
+
+/// a handle to a buffer the shader can write to (ie RWStructuredBuffer),
+/// which can be read back on the cpu
+#[derive(Debug)]
+pub struct MutableStorageBufferHandle<T> {
+    index: usize,
+    _phantom_data: PhantomData<T>,
+}
+
+pub(super) struct RawStorageBuffer {
+    pub(super) buffer: vk::Buffer,
+    pub(super) device_mem: vk::DeviceMemory,
+    pub(super) size: vk::DeviceSize,
+    /// for pointers to the buffer's elements, if the device supports buffer device addresses
+    pub(super) device_address: Option<vk::DeviceAddress>,
+    /// persistently mapped host-visible memory, for mutable buffers only
+    pub(super) mapped_mem: Option<*mut c_void>,
+}
+
+// NOTE renderer has to enforce type safety
+// unlike uniform buffers, these are not per-frame;
+// read-only buffers are device-local and only written on creation,
+// mutable buffers are host-visible and read back after the gpu is done with them
+pub(super) struct StorageBufferStorage(Vec<Option<RawStorageBuffer>>);
+
+impl StorageBufferStorage {
+    pub fn new() -> Self {
+        Self(Default::default())
+    }
+
+    pub fn add<T>(&mut self, storage_buffer: RawStorageBuffer) -> StorageBufferHandle<T> {
+        let handle = StorageBufferHandle {
+            index: self.0.len(),
+            _phantom_data: PhantomData::<T>,
+        };
+
+        self.0.push(Some(storage_buffer));
+
+        handle
+    }
+
+    pub fn add_mutable<T>(
+        &mut self,
+        storage_buffer: RawStorageBuffer,
+    ) -> MutableStorageBufferHandle<T> {
+        let handle = MutableStorageBufferHandle {
+            index: self.0.len(),
+            _phantom_data: PhantomData::<T>,
+        };
+
+        self.0.push(Some(storage_buffer));
+
+        handle
+    }
+
+    pub fn get_raw(&self, handle: &RawStorageBufferHandle) -> &RawStorageBuffer {
+        self.0[handle.index].as_ref().unwrap()
+    }
+
+    /// any bytes written by the gpu are a valid T, because it's Pod
+    pub fn get_mapped_elements<T: bytemuck::Pod>(
+        &self,
+        handle: &MutableStorageBufferHandle<T>,
+    ) -> &[T] {
+        let raw_storage_buffer = self.0[handle.index].as_ref().unwrap();
+        let mapped_mem = raw_storage_buffer.mapped_mem.unwrap();
+        let len = raw_storage_buffer.size as usize;
+
+        let bytes = unsafe { std::slice::from_raw_parts(mapped_mem as *const u8, len) };
+        bytemuck::cast_slice(bytes)
+    }
+
+    pub fn take<T>(&mut self, handle: StorageBufferHandle<T>) -> RawStorageBuffer {
+        self.0[handle.index].take().unwrap()
+    }
+
+    pub fn take_mutable<T>(&mut self, handle: MutableStorageBufferHandle<T>) -> RawStorageBuffer {
+        self.0[handle.index].take().unwrap()
+    }
+
+    pub fn take_all(&mut self) -> Vec<RawStorageBuffer> {
+        self.0
+            .iter_mut()
+            .filter_map(|option| option.take())
+            .collect()
+    }
+}
+
+// NOTE find a way to limit this to generated code
+//   would need to make PipelineConfig fields private
+pub struct RawStorageBufferHandle {
+    index: usize,
+}
+
+impl RawStorageBufferHandle {
+    pub fn from_typed<T>(handle: &StorageBufferHandle<T>) -> Self {
+        let index = handle.index;
+        Self { index }
+    }
+
+    pub fn from_mutable<T>(handle: &MutableStorageBufferHandle<T>) -> Self {
+        let index = handle.index;
+        Self { index }
+    }
+}
@@ -1,11 +1,75 @@
+use std::marker::PhantomData;
+
 use ash::vk;
 
+/// a texture, typed by the shape it was declared with in slang
 #[derive(Debug)]
-pub struct TextureHandle {
+pub struct TextureHandle<S = Texture2D> {
     #[expect(unused)] // for debugging
     #[cfg(debug_assertions)]
     source_file_name: String,
     index: usize,
+    _phantom_data: PhantomData<S>,
+}
+
+impl<S> TextureHandle<S> {
+    /// the texture's stable index in bindless texture arrays,
+    /// for writing into uniforms or push constants;
+    /// once the texture is dropped, a later texture can reuse the index
+    pub fn bindless_index(&self) -> u32 {
+        self.index as u32
+    }
+}
+
+/// the vulkan image and view types for a slang texture shape
+pub trait TextureShape {
+    const IMAGE_TYPE: vk::ImageType;
+    const VIEW_TYPE: vk::ImageViewType;
+    const CREATE_FLAGS: vk::ImageCreateFlags = vk::ImageCreateFlags::empty();
+    /// whether the input images are depth slices of one layer, rather than array layers
+    const DEPTH_SLICES: bool = false;
+}
+
+#[derive(Debug)]
+pub struct Texture1D;
+
+impl TextureShape for Texture1D {
+    const IMAGE_TYPE: vk::ImageType = vk::ImageType::TYPE_1D;
+    const VIEW_TYPE: vk::ImageViewType = vk::ImageViewType::TYPE_1D;
+}
+
+#[derive(Debug)]
+pub struct Texture2D;
+
+impl TextureShape for Texture2D {
+    const IMAGE_TYPE: vk::ImageType = vk::ImageType::TYPE_2D;
+    const VIEW_TYPE: vk::ImageViewType = vk::ImageViewType::TYPE_2D;
+}
+
+#[derive(Debug)]
+pub struct Texture3D;
+
+impl TextureShape for Texture3D {
+    const IMAGE_TYPE: vk::ImageType = vk::ImageType::TYPE_3D;
+    const VIEW_TYPE: vk::ImageViewType = vk::ImageViewType::TYPE_3D;
+    const DEPTH_SLICES: bool = true;
+}
+
+#[derive(Debug)]
+pub struct TextureCube;
+
+impl TextureShape for TextureCube {
+    const IMAGE_TYPE: vk::ImageType = vk::ImageType::TYPE_2D;
+    const VIEW_TYPE: vk::ImageViewType = vk::ImageViewType::CUBE;
+    const CREATE_FLAGS: vk::ImageCreateFlags = vk::ImageCreateFlags::CUBE_COMPATIBLE;
+}
+
+#[derive(Debug)]
+pub struct Texture2DArray;
+
+impl TextureShape for Texture2DArray {
+    const IMAGE_TYPE: vk::ImageType = vk::ImageType::TYPE_2D;
+    const VIEW_TYPE: vk::ImageViewType = vk::ImageViewType::TYPE_2D_ARRAY;
 }
 
 pub(super) struct TextureStorage(Vec<Option<Texture>>);
@@ -15,22 +79,47 @@ impl TextureStorage {
         Self(Default::default())
     }
 
-    pub fn add(&mut self, texture: Texture) -> TextureHandle {
+    pub fn add<S>(&mut self, texture: Texture) -> TextureHandle<S> {
+        let index = self.next_index();
         let handle = TextureHandle {
             #[cfg(debug_assertions)]
             source_file_name: texture.source_file_name.clone(),
-            index: self.0.len(),
+            index,
+            _phantom_data: PhantomData::<S>,
         };
-        self.0.push(Some(texture));
+        if index == self.0.len() {
+            self.0.push(Some(texture));
+        } else {
+            self.0[index] = Some(texture);
+        }
 
         handle
     }
 
-    pub fn get(&self, handle: &TextureHandle) -> &Texture {
+    pub fn get_raw(&self, handle: &RawTextureHandle) -> &Texture {
         self.0[handle.index].as_ref().unwrap()
     }
 
-    pub fn take(&mut self, handle: TextureHandle) -> Texture {
+    /// the index that the next added texture will have,
+    /// which is the first slot freed by a dropped texture, if any
+    pub fn next_index(&self) -> usize {
+        self.0
+            .iter()
+            .position(Option::is_none)
+            .unwrap_or(self.0.len())
+    }
+
+    /// live 2D textures with their bindless indices;
+    /// bindless arrays are always Texture2D[] (see Texture::is_bindless)
+    pub fn bindless_textures(&self) -> impl Iterator<Item = (usize, &Texture)> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(|(index, option)| Some((index, option.as_ref()?)))
+            .filter(|(_, texture)| texture.is_bindless())
+    }
+
+    pub fn take<S>(&mut self, handle: TextureHandle<S>) -> Texture {
         self.0[handle.index].take().unwrap()
     }
 
@@ -49,6 +138,32 @@ pub(super) struct Texture {
     pub(super) image_memory: vk::DeviceMemory,
     pub(super) image_view: vk::ImageView,
     pub(super) sampler: vk::Sampler,
+    /// from the TextureShape it was created with
+    pub(super) view_type: vk::ImageViewType,
     #[expect(unused)] // currently unused after init
     pub(super) mip_levels: u32,
 }
+
+impl Texture {
+    /// whether the texture goes in bindless arrays, which only hold 2D textures
+    pub(super) fn is_bindless(&self) -> bool {
+        self.view_type == vk::ImageViewType::TYPE_2D
+    }
+}
+
+// NOTE find a way to limit this to generated code
+//   would need to make PipelineConfig fields private
+pub struct RawTextureHandle {
+    index: usize,
+}
+
+impl RawTextureHandle {
+    pub fn from_typed<S>(handle: &TextureHandle<S>) -> Self {
+        let index = handle.index;
+        Self { index }
+    }
+
+    pub(super) fn bindless_index(&self) -> usize {
+        self.index
+    }
+}
@@ -39,14 +39,15 @@ impl UniformBufferStorage {
         self.0[handle.index].as_ref().unwrap()
     }
 
-    pub fn get_mapped_mem_for_frame<T>(
+    /// the frame's mapped memory, sized for one T
+    pub fn get_mapped_bytes_for_frame<T>(
         &mut self,
         handle: &mut UniformBufferHandle<T>,
         frame: usize,
-    ) -> &mut T {
+    ) -> &mut [u8] {
         let raw_uniform_buffer = &mut self.0[handle.index].as_mut().unwrap()[frame];
-        let mut_ptr = raw_uniform_buffer.mapped_mem as *mut T;
-        unsafe { &mut *mut_ptr }
+        let mut_ptr = raw_uniform_buffer.mapped_mem as *mut u8;
+        unsafe { std::slice::from_raw_parts_mut(mut_ptr, std::mem::size_of::<T>()) }
     }
 
     pub fn take<T>(&mut self, handle: UniformBufferHandle<T>) -> Vec<RawUniformBuffer> {
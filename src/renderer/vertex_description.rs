@@ -1,6 +1,19 @@
 use ash::vk;
 
-pub trait VertexDescription: super::GPUWrite {
+use super::gpu_write::VertexData;
+
+pub trait VertexDescription: VertexData {
     fn binding_descriptions() -> Vec<vk::VertexInputBindingDescription>;
     fn attribute_descriptions() -> Vec<vk::VertexInputAttributeDescription>;
 }
+
+/// no vertex input, for shaders that draw from SV_VertexID
+impl VertexDescription for () {
+    fn binding_descriptions() -> Vec<vk::VertexInputBindingDescription> {
+        vec![]
+    }
+
+    fn attribute_descriptions() -> Vec<vk::VertexInputAttributeDescription> {
+        vec![]
+    }
+}
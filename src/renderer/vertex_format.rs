@@ -0,0 +1,128 @@
+use bytemuck::{Pod, Zeroable};
+use serde::Serialize;
+
+/// two 8-bit unsigned normalized components
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct Unorm8x2(pub [u8; 2]);
+
+impl From<glam::Vec2> for Unorm8x2 {
+    fn from(vec: glam::Vec2) -> Self {
+        Self(vec.to_array().map(unorm8))
+    }
+}
+
+/// four 8-bit unsigned normalized components, ie an RGBA color
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct Unorm8x4(pub [u8; 4]);
+
+impl From<glam::Vec4> for Unorm8x4 {
+    fn from(vec: glam::Vec4) -> Self {
+        Self(vec.to_array().map(unorm8))
+    }
+}
+
+/// two 8-bit signed normalized components
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct Snorm8x2(pub [i8; 2]);
+
+impl From<glam::Vec2> for Snorm8x2 {
+    fn from(vec: glam::Vec2) -> Self {
+        Self(vec.to_array().map(snorm8))
+    }
+}
+
+/// four 8-bit signed normalized components
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct Snorm8x4(pub [i8; 4]);
+
+impl From<glam::Vec4> for Snorm8x4 {
+    fn from(vec: glam::Vec4) -> Self {
+        Self(vec.to_array().map(snorm8))
+    }
+}
+
+/// two 16-bit unsigned normalized components
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct Unorm16x2(pub [u16; 2]);
+
+impl From<glam::Vec2> for Unorm16x2 {
+    fn from(vec: glam::Vec2) -> Self {
+        Self(vec.to_array().map(unorm16))
+    }
+}
+
+/// four 16-bit unsigned normalized components
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct Unorm16x4(pub [u16; 4]);
+
+impl From<glam::Vec4> for Unorm16x4 {
+    fn from(vec: glam::Vec4) -> Self {
+        Self(vec.to_array().map(unorm16))
+    }
+}
+
+/// two 16-bit signed normalized components, ie an octahedral normal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct Snorm16x2(pub [i16; 2]);
+
+impl From<glam::Vec2> for Snorm16x2 {
+    fn from(vec: glam::Vec2) -> Self {
+        Self(vec.to_array().map(snorm16))
+    }
+}
+
+/// four 16-bit signed normalized components
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct Snorm16x4(pub [i16; 4]);
+
+impl From<glam::Vec4> for Snorm16x4 {
+    fn from(vec: glam::Vec4) -> Self {
+        Self(vec.to_array().map(snorm16))
+    }
+}
+
+/// two half-precision floats, ie texture coordinates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct Float16x2(pub [half::f16; 2]);
+
+impl From<glam::Vec2> for Float16x2 {
+    fn from(vec: glam::Vec2) -> Self {
+        Self(vec.to_array().map(half::f16::from_f32))
+    }
+}
+
+/// four half-precision floats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct Float16x4(pub [half::f16; 4]);
+
+impl From<glam::Vec4> for Float16x4 {
+    fn from(vec: glam::Vec4) -> Self {
+        Self(vec.to_array().map(half::f16::from_f32))
+    }
+}
+
+fn unorm8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * u8::MAX as f32).round() as u8
+}
+
+fn snorm8(value: f32) -> i8 {
+    (value.clamp(-1.0, 1.0) * i8::MAX as f32).round() as i8
+}
+
+fn unorm16(value: f32) -> u16 {
+    (value.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16
+}
+
+fn snorm16(value: f32) -> i16 {
+    (value.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16
+}
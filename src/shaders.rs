@@ -1,4 +1,5 @@
 use std::ffi::CString;
+use std::path::Path;
 
 use shader_slang as slang;
 use shader_slang::Downcast;
@@ -13,15 +14,33 @@ use json::*;
 /// whether to use column-major or row-major matricies with slang
 pub const COLUMN_MAJOR: bool = true;
 
+/// whether to use scalar layout for uniform and storage buffers, rather than std140/std430
+///   this requires VK_EXT_scalar_block_layout (promoted in vulkan 1.2)
+pub const SCALAR_BLOCK_LAYOUT: bool = false;
+
+// NOTE these are either vertex with an optional fragment, or compute only;
+// that's validated during reflection
 pub struct ReflectedShader {
-    pub vertex_shader: CompiledShader,
-    pub fragment_shader: CompiledShader,
+    pub vertex_shader: Option<CompiledShader>,
+    pub fragment_shader: Option<CompiledShader>,
+    pub compute_shader: Option<CompiledShader>,
     pub reflection_json: ReflectionJson,
 }
 
-fn prepare_reflected_shader(source_file_name: &str) -> anyhow::Result<ReflectedShader> {
+/// the suffix for a shader's sidecar variants file, ie 'lit.variants.json'
+const VARIANTS_FILE_SUFFIX: &str = ".variants.json";
+
+fn prepare_reflected_shader(
+    shaders_source_dir: &Path,
+    source_file_name: &str,
+    variant: Option<&ShaderVariant>,
+    scalar_block_layout: bool,
+) -> anyhow::Result<ReflectedShader> {
     let global_session = slang::GlobalSession::new().unwrap();
-    let search_path = CString::new("shaders/source").unwrap();
+    let search_path = CString::new(shaders_source_dir.to_str().unwrap()).unwrap();
+    // shared modules like attributes, for shaders outside of shaders/source (ie test fixtures)
+    let shared_modules_path = crate::util::manifest_path(["shaders", "source"]);
+    let shared_modules_search_path = CString::new(shared_modules_path.to_str().unwrap()).unwrap();
 
     let session_options = slang::CompilerOptions::default()
         .vulkan_use_entry_point_name(true)
@@ -33,13 +52,18 @@ fn prepare_reflected_shader(source_file_name: &str) -> anyhow::Result<ReflectedS
     } else {
         session_options.matrix_layout_row(true)
     };
+    let session_options = if scalar_block_layout {
+        session_options.glsl_force_scalar_layout(true)
+    } else {
+        session_options
+    };
 
     let target_desc = slang::TargetDesc::default()
         .format(slang::CompileTarget::Spirv)
         .profile(global_session.find_profile("glsl_450+spirv_1_6"));
 
     let targets = [target_desc];
-    let search_paths = [search_path.as_ptr()];
+    let search_paths = [search_path.as_ptr(), shared_modules_search_path.as_ptr()];
     let session_desc = slang::SessionDesc::default()
         .targets(&targets)
         .search_paths(&search_paths)
@@ -49,37 +73,52 @@ fn prepare_reflected_shader(source_file_name: &str) -> anyhow::Result<ReflectedS
 
     let module = session.load_module(source_file_name)?;
 
-    // the examples have 1 vert and 1 frag shader
-    debug_assert!(module.entry_points().len() == 2);
+    // link-time specialization; the variant module exports the shader's extern types
+    let variant_module = match variant {
+        Some(variant) => Some(session.load_module(&variant.module_name)?),
+        None => None,
+    };
 
     let mut components = vec![module.downcast().clone()];
+    if let Some(variant_module) = &variant_module {
+        components.push(variant_module.downcast().clone());
+    }
     let mut vertex_shader: Option<CompiledShader> = None;
     let mut fragment_shader: Option<CompiledShader> = None;
+    let mut compute_shader: Option<CompiledShader> = None;
     for entry_point in module.entry_points() {
-        let compiled_shader = compile_shader(&entry_point, &session, &module)?;
-
-        if compiled_shader.stage == slang::Stage::Vertex {
-            vertex_shader = Some(compiled_shader)
-        } else if compiled_shader.stage == slang::Stage::Fragment {
-            fragment_shader = Some(compiled_shader)
+        let compiled_shader =
+            compile_shader(&entry_point, &session, &module, variant_module.as_ref())?;
+
+        let existing = match compiled_shader.stage {
+            slang::Stage::Vertex => vertex_shader.replace(compiled_shader),
+            slang::Stage::Fragment => fragment_shader.replace(compiled_shader),
+            slang::Stage::Compute => compute_shader.replace(compiled_shader),
+            s => anyhow::bail!("unsupported entry point stage {s:?} in: {source_file_name}"),
+        };
+        if existing.is_some() {
+            anyhow::bail!("multiple entry points for the same stage in: {source_file_name}");
         }
 
         components.push(entry_point.downcast().clone());
     }
-    let vertex_shader = vertex_shader
-        .unwrap_or_else(|| panic!("failed to load vertex entry point for: {source_file_name}"));
-    let fragment_shader = fragment_shader
-        .unwrap_or_else(|| panic!("failed to load fragment entry point for: {source_file_name}"));
 
     let program = session.create_composite_component_type(&components)?;
     let linked_program = program.link()?;
     let program_layout = linked_program.layout(0)?;
 
-    let reflection_json = reflection::reflection_json(source_file_name, program_layout)?;
+    let imported_types = imported_types(&session, &module)?;
+    let reflection_json = reflection::reflection_json(
+        source_file_name,
+        program_layout,
+        imported_types,
+        variant.cloned(),
+    )?;
 
     let reflected_shader = ReflectedShader {
         vertex_shader,
         fragment_shader,
+        compute_shader,
         reflection_json,
     };
 
@@ -87,8 +126,71 @@ fn prepare_reflected_shader(source_file_name: &str) -> anyhow::Result<ReflectedS
 }
 
 #[cfg(debug_assertions)]
-pub fn dev_compile_slang_shaders(source_file_name: &str) -> anyhow::Result<ReflectedShader> {
-    prepare_reflected_shader(source_file_name)
+pub fn dev_compile_slang_shaders(
+    source_file_name: &str,
+    variant: Option<&ShaderVariant>,
+) -> anyhow::Result<ReflectedShader> {
+    prepare_reflected_shader(
+        Path::new("shaders/source"),
+        source_file_name,
+        variant,
+        SCALAR_BLOCK_LAYOUT,
+    )
+}
+
+/// reads the shader's sidecar variants file, if it has one
+fn shader_variants(
+    shaders_source_dir: &Path,
+    source_file_name: &str,
+) -> anyhow::Result<Vec<ShaderVariant>> {
+    let variants_file_name =
+        source_file_name.replace(build_tasks::SHADER_FILE_SUFFIX, VARIANTS_FILE_SUFFIX);
+    let variants_path = shaders_source_dir.join(variants_file_name);
+    if !std::fs::exists(&variants_path)? {
+        return Ok(vec![]);
+    }
+
+    let variants_json = std::fs::read_to_string(&variants_path)?;
+    let variants: Vec<ShaderVariant> = serde_json::from_str(&variants_json)?;
+    if variants.is_empty() {
+        anyhow::bail!("empty variants file: {variants_path:?}");
+    }
+
+    Ok(variants)
+}
+
+/// finds the structs declared in the shader's non-shader dependencies (ie, mvp.slang),
+/// so that codegen can share one definition between shaders
+fn imported_types(
+    session: &slang::Session,
+    module: &slang::Module,
+) -> anyhow::Result<Vec<ImportedType>> {
+    let mut imported_types = vec![];
+
+    for dependency_path in module.dependency_file_paths() {
+        let dependency_path = std::path::Path::new(dependency_path);
+        let Some(file_name) = dependency_path.file_name().and_then(|f| f.to_str()) else {
+            continue;
+        };
+        if file_name.ends_with(build_tasks::SHADER_FILE_SUFFIX) {
+            continue;
+        }
+        let Some(module_name) = file_name.strip_suffix(".slang") else {
+            continue;
+        };
+
+        let dependency = session.load_module(module_name)?;
+        for decl in dependency.module_reflection().children() {
+            if decl.kind() == slang::DeclKind::Struct {
+                imported_types.push(ImportedType {
+                    type_name: decl.name().to_string(),
+                    module_name: module_name.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(imported_types)
 }
 
 pub struct CompiledShader {
@@ -119,11 +221,15 @@ fn compile_shader(
     entry_point: &slang::EntryPoint,
     session: &slang::Session,
     module: &slang::Module,
+    variant_module: Option<&slang::Module>,
 ) -> anyhow::Result<CompiledShader> {
-    let program = session.create_composite_component_type(&[
-        module.downcast().clone(),
-        entry_point.downcast().clone(),
-    ])?;
+    let mut components = vec![module.downcast().clone()];
+    if let Some(variant_module) = variant_module {
+        components.push(variant_module.downcast().clone());
+    }
+    components.push(entry_point.downcast().clone());
+
+    let program = session.create_composite_component_type(&components)?;
 
     let linked_program = program.link()?;
 
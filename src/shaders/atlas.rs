@@ -2,15 +2,16 @@ use std::ffi::CString;
 
 use ash::vk;
 
-use crate::renderer::LayoutDescription;
+use crate::renderer::{LayoutDescription, SpecializationConstants};
 
-use super::json::ReflectedPipelineLayout;
+use super::json::{ReflectedPipelineLayout, ShaderVariant};
 
 pub trait ShaderAtlasEntry {
     // dev only
 
     // used in hot reload
     fn source_file_name(&self) -> &str;
+    fn variant(&self) -> Option<&ShaderVariant>;
 
     // dev and release
 
@@ -20,6 +21,9 @@ pub trait ShaderAtlasEntry {
     // one set of descriptions per descriptor set
     fn layout_bindings(&self) -> Vec<Vec<LayoutDescription>>;
 
+    /// values set with the generated shader's `with_*` methods
+    fn specialization_constants(&self) -> SpecializationConstants;
+
     // release only
 
     fn precompiled_shaders(&self) -> PrecompiledShaders;
@@ -27,9 +31,36 @@ pub trait ShaderAtlasEntry {
     fn pipeline_layout(&self) -> &ReflectedPipelineLayout;
 }
 
+/// like ShaderAtlasEntry, for shaders with only a compute entry point
+pub trait ComputeShaderAtlasEntry {
+    // dev only
+
+    // used in hot reload
+    fn source_file_name(&self) -> &str;
+    fn variant(&self) -> Option<&ShaderVariant>;
+
+    // dev and release
+
+    /// the entry point's numthreads
+    fn thread_group_size(&self) -> [u32; 3];
+
+    // one set of descriptions per descriptor set
+    fn layout_bindings(&self) -> Vec<Vec<LayoutDescription>>;
+
+    /// values set with the generated shader's `with_*` methods
+    fn specialization_constants(&self) -> SpecializationConstants;
+
+    // release only
+
+    fn precompiled_shader(&self) -> PrecompiledShader;
+
+    fn pipeline_layout(&self) -> &ReflectedPipelineLayout;
+}
+
 pub struct PrecompiledShaders {
     pub vert: PrecompiledShader,
-    pub frag: PrecompiledShader,
+    /// None for depth-only pipelines
+    pub frag: Option<PrecompiledShader>,
 }
 
 pub struct PrecompiledShader {
@@ -5,7 +5,7 @@ use heck::ToSnakeCase;
 
 use crate::util::relative_path;
 
-use super::{ReflectedShader, json::*, prepare_reflected_shader};
+use super::{ReflectedShader, json::*, prepare_reflected_shader, shader_variants};
 
 pub struct Config {
     /// whether to write rust code (or only shader spirv & json)
@@ -16,9 +16,11 @@ pub struct Config {
     pub shaders_source_dir: PathBuf,
     /// the directory to write shader spriv & json to
     pub compiled_shaders_dir: PathBuf,
+    /// whether to compile with scalar layout; see shaders::SCALAR_BLOCK_LAYOUT
+    pub scalar_block_layout: bool,
 }
 
-const SHADER_FILE_SUFFIX: &str = ".shader.slang";
+pub(crate) const SHADER_FILE_SUFFIX: &str = ".shader.slang";
 
 pub fn write_precompiled_shaders(config: Config) -> anyhow::Result<()> {
     let slang_file_names: Vec<_> = std::fs::read_dir(&config.shaders_source_dir)?
@@ -36,46 +38,87 @@ pub fn write_precompiled_shaders(config: Config) -> anyhow::Result<()> {
         })
         .collect();
 
+    // shaders with a variants file are compiled once per variant, and never unspecialized
+    let mut shader_sources: Vec<(String, Option<ShaderVariant>)> = vec![];
+    for slang_file_name in slang_file_names {
+        let variants = shader_variants(&config.shaders_source_dir, &slang_file_name)?;
+        if variants.is_empty() {
+            shader_sources.push((slang_file_name, None));
+        } else {
+            for variant in variants {
+                shader_sources.push((slang_file_name.clone(), Some(variant)));
+            }
+        }
+    }
+
     let mut generated_source_files = vec![];
 
     // generate top-level rust modules
     if config.generate_rust_source {
-        add_top_level_rust_modules(&slang_file_names, &mut generated_source_files);
+        let module_names: Vec<String> = shader_sources
+            .iter()
+            .map(|(slang_file_name, variant)| shader_name(slang_file_name, variant.as_ref()))
+            .collect();
+        add_top_level_rust_modules(module_names, &mut generated_source_files);
     }
 
     if std::fs::exists(&config.compiled_shaders_dir)? {
         std::fs::remove_dir_all(&config.compiled_shaders_dir)?;
     }
 
+    // structs from imported modules, shared between per-shader files
+    let mut shared_struct_defs = vec![];
+
     // generate per-shader files
-    for slang_file_name in &slang_file_names {
+    for (slang_file_name, variant) in &shader_sources {
         let ReflectedShader {
             vertex_shader,
             fragment_shader,
+            compute_shader,
             reflection_json,
-        } = prepare_reflected_shader(slang_file_name)?;
+        } = prepare_reflected_shader(
+            &config.shaders_source_dir,
+            slang_file_name,
+            variant.as_ref(),
+            config.scalar_block_layout,
+        )?;
 
         if config.generate_rust_source {
-            let source_file = build_generated_source_file(&reflection_json);
+            let source_file = build_generated_source_file(
+                &reflection_json,
+                config.scalar_block_layout,
+                &mut shared_struct_defs,
+            );
             generated_source_files.push(source_file);
         }
 
-        let source_file_name = &reflection_json.source_file_name;
+        let shader_name = shader_name(slang_file_name, variant.as_ref());
 
         std::fs::create_dir_all(&config.compiled_shaders_dir)?;
 
         let reflection_json = serde_json::to_string_pretty(&reflection_json)?;
-        let reflection_json_file_name = source_file_name.replace(SHADER_FILE_SUFFIX, ".json");
+        let reflection_json_file_name = format!("{shader_name}.json");
         let json_path = &config.compiled_shaders_dir.join(&reflection_json_file_name);
         std::fs::write(json_path, reflection_json)?;
 
-        let spv_vert_file_name = source_file_name.replace(SHADER_FILE_SUFFIX, ".vert.spv");
-        let vert_path = &config.compiled_shaders_dir.join(&spv_vert_file_name);
-        std::fs::write(vert_path, vertex_shader.shader_bytecode.as_slice())?;
+        let compiled_shaders = [
+            (vertex_shader, ".vert.spv"),
+            (fragment_shader, ".frag.spv"),
+            (compute_shader, ".comp.spv"),
+        ];
+        for (compiled_shader, spv_suffix) in compiled_shaders {
+            let Some(compiled_shader) = compiled_shader else {
+                continue;
+            };
+
+            let spv_file_name = format!("{shader_name}{spv_suffix}");
+            let spv_path = &config.compiled_shaders_dir.join(&spv_file_name);
+            std::fs::write(spv_path, compiled_shader.shader_bytecode.as_slice())?;
+        }
+    }
 
-        let spv_frag_file_name = source_file_name.replace(SHADER_FILE_SUFFIX, ".frag.spv");
-        let frag_path = &config.compiled_shaders_dir.join(&spv_frag_file_name);
-        std::fs::write(frag_path, fragment_shader.shader_bytecode.as_slice())?;
+    if config.generate_rust_source {
+        generated_source_files.push(build_shared_source_file(shared_struct_defs));
     }
 
     let generated_dir = config.rust_source_dir.join("generated");
@@ -90,14 +133,20 @@ pub fn write_precompiled_shaders(config: Config) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// the generated module and compiled file name, ie 'lit_phong' for lit.shader.slang
+fn shader_name(slang_file_name: &str, variant: Option<&ShaderVariant>) -> String {
+    let shader_name = slang_file_name.replace(SHADER_FILE_SUFFIX, "");
+
+    match variant {
+        Some(variant) => format!("{shader_name}_{}", variant.variant_name.to_snake_case()),
+        None => shader_name,
+    }
+}
+
 fn add_top_level_rust_modules(
-    slang_file_names: &[String],
+    module_names: Vec<String>,
     generated_source_files: &mut Vec<GeneratedFile>,
 ) {
-    let module_names: Vec<String> = slang_file_names
-        .iter()
-        .map(|file_name| file_name.replace(SHADER_FILE_SUFFIX, ""))
-        .collect();
     let entries: Vec<(String, String)> = module_names
         .iter()
         .map(|module_name| {
@@ -120,33 +169,77 @@ fn add_top_level_rust_modules(
 
     let top_generated_module = GeneratedFile {
         relative_path: relative_path(["generated.rs"]),
-        content: "pub mod shader_atlas;".to_string(),
+        content: "pub mod shader_atlas;\npub mod shared;".to_string(),
     };
     generated_source_files.push(top_generated_module);
 }
 
-fn build_generated_source_file(reflection_json: &ReflectionJson) -> GeneratedFile {
+fn build_shared_source_file(
+    mut shared_struct_defs: Vec<GeneratedStructDefinition>,
+) -> GeneratedFile {
+    // NOTE shader files may be visited in any order
+    shared_struct_defs.sort_by(|a, b| a.type_name.cmp(&b.type_name));
+
+    GeneratedFile {
+        relative_path: relative_path(["generated", "shared.rs"]),
+        content: SharedModule {
+            struct_defs: shared_struct_defs,
+        }
+        .render()
+        .unwrap(),
+    }
+}
+
+fn build_generated_source_file(
+    reflection_json: &ReflectionJson,
+    scalar_block_layout: bool,
+    shared_struct_defs: &mut Vec<GeneratedStructDefinition>,
+) -> GeneratedFile {
     let mut struct_defs = vec![];
     let mut vertex_impl_blocks = vec![];
-    let mut required_resources = vec![
-        RequiredResource {
-            field_name: "vertices".to_string(),
-            resource_type: RequiredResourceType::VertexBuffer,
-        },
-        RequiredResource {
-            field_name: "indices".to_string(),
-            resource_type: RequiredResourceType::IndexBuffer,
-        },
-    ];
+    let mut required_resources = vec![];
 
     let mut vertex_type_name = None;
-    for vert_param in &reflection_json.vertex_entry_point.parameters {
+    let mut instance_type_name = None;
+    let vertex_params = reflection_json
+        .vertex_entry_point
+        .iter()
+        .flat_map(|entry_point| &entry_point.parameters);
+    for vert_param in vertex_params {
         match vert_param {
             EntryPointParameter::Scalar(ScalarEntryPointParameter::Semantic(_)) => {}
-            EntryPointParameter::Scalar(ScalarEntryPointParameter::Bound(_)) => todo!(),
+            EntryPointParameter::Vector(VectorEntryPointParameter::Semantic(_)) => {}
+
+            // push constants; see gather_push_constants
+            EntryPointParameter::Scalar(ScalarEntryPointParameter::Bound(
+                BoundScalarEntryPointParameter {
+                    binding: Binding::Uniform(_),
+                    ..
+                },
+            )) => {}
+            EntryPointParameter::Struct(StructEntryPointParameter {
+                binding: Binding::Uniform(_),
+                ..
+            }) => {}
+
+            EntryPointParameter::Scalar(ScalarEntryPointParameter::Bound(_))
+            | EntryPointParameter::Vector(VectorEntryPointParameter::Bound(_)) => {
+                panic!("vertex inputs outside of a struct parameter are not supported")
+            }
 
             EntryPointParameter::Struct(struct_param) => {
-                vertex_type_name = Some(struct_param.type_name.to_string());
+                // per-instance input gets its own vertex buffer binding
+                let per_instance =
+                    user_attribute(&struct_param.user_attributes, "PerInstance").is_some();
+                if per_instance {
+                    instance_type_name = Some(struct_param.type_name.to_string());
+                } else if vertex_type_name.is_some() {
+                    panic!(
+                        "multiple per-vertex struct parameters; mark instance data with [PerInstance]"
+                    );
+                } else {
+                    vertex_type_name = Some(struct_param.type_name.to_string());
+                }
 
                 let mut generated_fields = vec![];
                 for field in &struct_param.fields {
@@ -155,32 +248,54 @@ fn build_generated_source_file(reflection_json: &ReflectionJson) -> GeneratedFil
                     };
                 }
 
-                let def = GeneratedStructDefinition {
-                    type_name: struct_param.type_name.to_string(),
-                    fields: generated_fields,
-                    gpu_write: true,
-                    trait_derives: vec!["Debug", "Clone", "Serialize"],
+                // locations continue across struct parameters, in declaration order
+                let Binding::VaryingInput(IndexCountBinding { index, .. }) = &struct_param.binding
+                else {
+                    panic!(
+                        "vertex struct parameter without varying input binding: {struct_param:?}"
+                    );
                 };
 
+                // vertex attributes have locations rather than a uniform layout,
+                // so the offsets follow repr(C), with explicit padding for any gaps;
+                // otherwise Pod fails to derive
                 let mut attribute_descriptions = vec![];
-                for (location, field) in def.fields.iter().enumerate() {
-                    let format = match field.type_name.as_str() {
-                        // TODO use an enum for supported glam types
-                        "glam::Vec3" => "ash::vk::Format::R32G32B32_SFLOAT",
-                        "glam::Vec2" => "ash::vk::Format::R32G32_SFLOAT",
-                        _ => todo!(),
-                    };
+                let mut location = *index;
+                let mut end: usize = 0;
+                let mut struct_alignment = 1;
+                for field in &mut generated_fields {
+                    let (format, location_count) = vertex_attribute_format(&field.type_name);
+                    let (size, alignment) = vertex_attribute_layout(&field.type_name, format);
+
+                    let offset = end.next_multiple_of(alignment);
+                    field.offset = Some(offset);
+                    field.size = Some(size);
+                    end = offset + size;
+                    struct_alignment = struct_alignment.max(alignment);
 
                     let attr = VertexAttributeDescription {
-                        field_name: field.field_name.to_snake_case(),
-                        format: format.to_string(),
+                        field_name: field.field_name.clone(),
+                        format: format!("ash::vk::Format::{format}"),
                         location,
                     };
 
                     attribute_descriptions.push(attr);
+                    location += location_count;
                 }
+
+                let def = GeneratedStructDefinition {
+                    type_name: struct_param.type_name.to_string(),
+                    fields: generated_fields,
+                    gpu_write: true,
+                    alignment: None,
+                    size: Some(end.next_multiple_of(struct_alignment)),
+                    trait_derives: vec!["Debug", "Clone", "Copy", "Serialize"],
+                    usage_traits: vec!["VertexData"],
+                };
                 let vert_block = VertexImplBlock {
                     type_name: def.type_name.clone(),
+                    binding: if per_instance { 1 } else { 0 },
+                    input_rate: if per_instance { "INSTANCE" } else { "VERTEX" },
                     attribute_descriptions,
                 };
                 vertex_impl_blocks.push(vert_block);
@@ -190,107 +305,173 @@ fn build_generated_source_file(reflection_json: &ReflectionJson) -> GeneratedFil
         }
     }
 
-    for GlobalParameter::ParameterBlock(parameter_block) in &reflection_json.global_parameters {
-        let mut param_block_fields = vec![];
-        for field in &parameter_block.element_type.fields {
-            if let Some(generated_field) = gather_struct_defs(field, &mut struct_defs) {
-                param_block_fields.push(generated_field);
-            };
-
-            if let Some(req) = required_resource(field) {
-                required_resources.push(req);
+    if reflection_json.vertex_entry_point.is_some() {
+        match &vertex_type_name {
+            Some(_) => {
+                required_resources.push(RequiredResource {
+                    field_name: "vertices".to_string(),
+                    resource_type: RequiredResourceType::VertexBuffer,
+                });
+                required_resources.push(RequiredResource {
+                    field_name: "indices".to_string(),
+                    resource_type: RequiredResourceType::IndexBuffer,
+                });
             }
-        }
 
-        let type_name = &parameter_block.element_type.type_name;
-        struct_defs.push(GeneratedStructDefinition {
-            type_name: type_name.to_string(),
-            fields: param_block_fields,
-            gpu_write: true,
-            trait_derives: vec!["Debug", "Clone", "Serialize"],
+            // geometry from SV_VertexID, ie a fullscreen triangle
+            None => required_resources.push(RequiredResource {
+                field_name: "vertex_count".to_string(),
+                resource_type: RequiredResourceType::VertexCount,
+            }),
+        }
+    }
+    if let Some(instance_type_name) = &instance_type_name {
+        required_resources.push(RequiredResource {
+            field_name: "instances".to_string(),
+            resource_type: RequiredResourceType::InstanceBuffer(instance_type_name.clone()),
         });
+    }
 
-        // the default-added parameter block uniform buffer
-        let param_name = parameter_block.parameter_name.to_snake_case();
-        let element_type_name = parameter_block.element_type.type_name.clone();
-        required_resources.push(RequiredResource {
-            field_name: format!("{param_name}_buffer"),
-            // TODO type name
-            resource_type: RequiredResourceType::UniformBuffer(element_type_name),
-        })
+    let mut descriptor_sets = vec![];
+    for GlobalParameter::ParameterBlock(parameter_block) in &reflection_json.global_parameters {
+        let param_block_resources = gather_parameter_block(
+            &parameter_block.parameter_name,
+            &parameter_block.element_type,
+            &parameter_block.parameter_name,
+            "resources",
+            &mut struct_defs,
+            &mut descriptor_sets,
+        );
+        required_resources.extend(param_block_resources);
+    }
+    let descriptor_sets: Vec<GeneratedDescriptorSet> =
+        descriptor_sets.into_iter().flatten().collect();
+
+    let push_constants_def = gather_push_constants(reflection_json, &mut struct_defs);
+    let push_constants_type_name = push_constants_def.as_ref().map(|def| def.type_name.clone());
+    if let Some(push_constants_def) = push_constants_def {
+        struct_defs.push(push_constants_def);
     }
 
     struct_defs.reverse();
 
-    let vertex_type_name = vertex_type_name.expect("no struct parameter for vertex entry point");
-    let resources_fields = required_resources
-        .iter()
-        .map(|r| {
-            let type_name = match &r.resource_type {
-                RequiredResourceType::VertexBuffer => format!("Vec<{vertex_type_name}>"),
-                RequiredResourceType::IndexBuffer => "Vec<u32>".to_string(),
-                RequiredResourceType::Texture => "&'a TextureHandle".to_string(),
-                RequiredResourceType::UniformBuffer(element_type_name) => {
-                    format!("&'a UniformBufferHandle<{element_type_name}>")
-                }
-            };
+    if scalar_block_layout {
+        for def in &mut struct_defs {
+            def.use_scalar_layout();
+        }
+    }
 
-            GeneratedStructFieldDefinition {
-                field_name: r.field_name.clone(),
-                type_name,
-            }
-        })
+    // structs from imported modules are defined once in generated::shared,
+    // and re-exported from here
+    let is_imported = |type_name: &str| {
+        reflection_json
+            .imported_types
+            .iter()
+            .any(|imported| imported.type_name == type_name)
+    };
+    // along with the padded array elements that they use
+    let imported_element_types: Vec<String> = struct_defs
+        .iter()
+        .filter(|def| is_imported(&def.type_name))
+        .flat_map(|def| &def.fields)
+        .filter_map(|field| field.array_element.as_ref())
+        .map(|element| element.type_name.clone())
+        .collect();
+    let (imported_struct_defs, mut struct_defs): (Vec<_>, Vec<_>) =
+        struct_defs.into_iter().partition(|def| {
+            is_imported(&def.type_name) || imported_element_types.contains(&def.type_name)
+        });
+    let shared_type_names: Vec<String> = imported_struct_defs
+        .iter()
+        .map(|def| def.type_name.clone())
         .collect();
+    for def in imported_struct_defs {
+        push_struct_def(shared_struct_defs, def);
+    }
 
-    let resources_struct = GeneratedStructDefinition {
-        type_name: "Resources<'a>".to_string(),
-        fields: resources_fields,
-        gpu_write: false,
-        trait_derives: vec![],
-    };
+    let resources_struct = resources_struct_def(
+        "Resources<'a>",
+        &required_resources,
+        vertex_type_name.as_deref(),
+    );
     struct_defs.push(resources_struct);
 
-    let shader_name = reflection_json
-        .source_file_name
-        .replace(SHADER_FILE_SUFFIX, "");
-    let file_name = reflection_json
-        .source_file_name
-        .replace(SHADER_FILE_SUFFIX, ".rs");
+    let shader_name = shader_name(
+        &reflection_json.source_file_name,
+        reflection_json.variant.as_ref(),
+    );
+    let file_name = format!("{shader_name}.rs");
     let relative_file_path = relative_path(["generated", "shader_atlas", &file_name]);
 
-    // NOTE these must be in descriptor set layout order in the reflection json
-    let resources_texture_fields: Vec<String> = required_resources
-        .iter()
-        .filter(|r| matches!(r.resource_type, RequiredResourceType::Texture))
-        .map(|r| r.field_name.clone())
-        .collect();
-    let resources_uniform_buffer_fields: Vec<String> = required_resources
+    let mut module_doc_lines = vec![format!(
+        "generated from slang shader: {}",
+        reflection_json.source_file_name
+    )];
+    if let Some(variant) = &reflection_json.variant {
+        module_doc_lines.push(format!(
+            "specialized with variant module: {}",
+            variant.module_name
+        ));
+    }
+
+    let specialization_setters: Vec<_> = reflection_json
+        .specialization_constants
         .iter()
-        .filter(|r| matches!(r.resource_type, RequiredResourceType::UniformBuffer(_)))
-        .map(|r| r.field_name.clone())
+        .map(specialization_setter)
         .collect();
 
-    let shader_impl = GeneratedShaderImpl {
-        shader_name: shader_name.clone(),
-        shader_type_name: "Shader".to_string(),
-        vertex_type_name,
-        resources_texture_fields,
-        resources_uniform_buffer_fields,
+    let content = match &reflection_json.compute_entry_point {
+        Some(compute_entry_point) => {
+            let thread_group_size = compute_entry_point
+                .thread_group_size
+                .expect("no thread group size for compute entry point");
+
+            let shader_impl = GeneratedComputeShaderImpl {
+                shader_name: shader_name.clone(),
+                shader_type_name: "Shader".to_string(),
+                thread_group_size,
+                push_constants_type_name,
+                descriptor_sets,
+                specialization_setters,
+            };
+
+            ComputeShaderAtlasEntryModule {
+                module_doc_lines,
+                shared_type_names,
+                struct_defs,
+                shader_impl,
+            }
+            .render()
+            .unwrap()
+        }
+
+        None => {
+            let shader_impl = GeneratedShaderImpl {
+                shader_name: shader_name.clone(),
+                shader_type_name: "Shader".to_string(),
+                vertex_type_name,
+                instance_type_name,
+                push_constants_type_name,
+                has_fragment_shader: reflection_json.fragment_entry_point.is_some(),
+                descriptor_sets,
+                specialization_setters,
+            };
+
+            ShaderAtlasEntryModule {
+                module_doc_lines,
+                shared_type_names,
+                struct_defs,
+                vertex_impl_blocks,
+                shader_impl,
+            }
+            .render()
+            .unwrap()
+        }
     };
 
     GeneratedFile {
         relative_path: relative_file_path,
-        content: ShaderAtlasEntryModule {
-            module_doc_lines: vec![format!(
-                "generated from slang shader: {}",
-                reflection_json.source_file_name
-            )],
-            struct_defs,
-            vertex_impl_blocks,
-            shader_impl,
-        }
-        .render()
-        .unwrap(),
+        content,
     }
 }
 
@@ -306,17 +487,431 @@ struct ShaderAtlasModule {
 #[template(path = "shader_atlas_entry.rs.askama", escape = "none")]
 struct ShaderAtlasEntryModule {
     module_doc_lines: Vec<String>,
+    /// re-exported from generated::shared
+    shared_type_names: Vec<String>,
     struct_defs: Vec<GeneratedStructDefinition>,
     vertex_impl_blocks: Vec<VertexImplBlock>,
     shader_impl: GeneratedShaderImpl,
 }
 
+#[derive(Template)]
+#[template(path = "shared.rs.askama", escape = "none")]
+struct SharedModule {
+    struct_defs: Vec<GeneratedStructDefinition>,
+}
+
 struct GeneratedShaderImpl {
     shader_name: String,
     shader_type_name: String,
-    vertex_type_name: String,
-    resources_texture_fields: Vec<String>,
-    resources_uniform_buffer_fields: Vec<String>,
+    /// None for a shader without vertex buffers, which draws from SV_VertexID
+    vertex_type_name: Option<String>,
+    /// the type of a [PerInstance] vertex entry point parameter
+    instance_type_name: Option<String>,
+    /// the generated PushConstants struct, if the shader has push constants
+    push_constants_type_name: Option<String>,
+    /// false for depth-only pipelines
+    has_fragment_shader: bool,
+    /// in descriptor set layout order
+    descriptor_sets: Vec<GeneratedDescriptorSet>,
+    specialization_setters: Vec<GeneratedSpecializationSetter>,
+}
+
+impl GeneratedShaderImpl {
+    /// the generic arguments for the generated PipelineConfig
+    fn pipeline_config_type_args(&self) -> String {
+        let vertex_type_name = self.vertex_type_name.as_deref().unwrap_or("()");
+
+        match (&self.instance_type_name, &self.push_constants_type_name) {
+            (instance_type_name, Some(push_constants_type_name)) => {
+                let instance_type_name = instance_type_name.as_deref().unwrap_or("()");
+                format!("{vertex_type_name}, {instance_type_name}, {push_constants_type_name}")
+            }
+            (Some(instance_type_name), None) => format!("{vertex_type_name}, {instance_type_name}"),
+            (None, None) => vertex_type_name.to_string(),
+        }
+    }
+
+    /// the types with VertexDescription impls, in vertex buffer binding order
+    fn vertex_input_type_names(&self) -> Vec<&str> {
+        self.vertex_type_name
+            .iter()
+            .chain(&self.instance_type_name)
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+#[derive(Template)]
+#[template(path = "shader_atlas_compute_entry.rs.askama", escape = "none")]
+struct ComputeShaderAtlasEntryModule {
+    module_doc_lines: Vec<String>,
+    /// re-exported from generated::shared
+    shared_type_names: Vec<String>,
+    struct_defs: Vec<GeneratedStructDefinition>,
+    shader_impl: GeneratedComputeShaderImpl,
+}
+
+struct GeneratedComputeShaderImpl {
+    shader_name: String,
+    shader_type_name: String,
+    /// the entry point's numthreads
+    thread_group_size: [u32; 3],
+    /// the generated PushConstants struct, if the shader has push constants
+    push_constants_type_name: Option<String>,
+    /// in descriptor set layout order
+    descriptor_sets: Vec<GeneratedDescriptorSet>,
+    specialization_setters: Vec<GeneratedSpecializationSetter>,
+}
+
+impl GeneratedComputeShaderImpl {
+    /// the generated ComputePipelineConfig type
+    fn pipeline_config_type(&self) -> String {
+        match &self.push_constants_type_name {
+            Some(push_constants_type_name) => {
+                format!("ComputePipelineConfig<{push_constants_type_name}>")
+            }
+            None => "ComputePipelineConfig".to_string(),
+        }
+    }
+}
+
+/// a builder method on the generated shader, ie 'with_max_lights'
+struct GeneratedSpecializationSetter {
+    constant_name: String,
+    method_name: String,
+    constant_id: u32,
+    type_name: String,
+    /// the expression for the value's bytes
+    bytes: String,
+}
+
+fn specialization_setter(constant: &SpecializationConstant) -> GeneratedSpecializationSetter {
+    let (type_name, bytes) = match constant.scalar_type {
+        // vulkan bool constants are a 4 byte VkBool32
+        ScalarType::Bool => ("bool", "u32::from(value).to_ne_bytes()"),
+        scalar_type => (scalar_type_name(scalar_type), "value.to_ne_bytes()"),
+    };
+
+    GeneratedSpecializationSetter {
+        constant_name: constant.constant_name.clone(),
+        method_name: format!("with_{}", constant.constant_name.to_snake_case()),
+        constant_id: constant.constant_id,
+        type_name: type_name.to_string(),
+        bytes: bytes.to_string(),
+    }
+}
+
+/// gathers the struct defs, resources and descriptor sets for a ParameterBlock,
+/// including any nested ParameterBlocks
+fn gather_parameter_block(
+    parameter_name: &str,
+    element_type: &ParameterBlockElementType,
+    // the prefix for the uniform buffer's resources field, ie 'scene' for 'scene_buffer'
+    buffer_name: &str,
+    // the path to this block's resources in the generated pipeline_config
+    resources_path: &str,
+    struct_defs: &mut Vec<GeneratedStructDefinition>,
+    descriptor_sets: &mut Vec<Option<GeneratedDescriptorSet>>,
+) -> Vec<RequiredResource> {
+    // reserve a slot, so that nested blocks come after their parent,
+    // matching the order in the reflected pipeline layout
+    let set_index = descriptor_sets.len();
+    descriptor_sets.push(None);
+
+    let mut param_block_fields = vec![];
+    let mut param_block_resources = vec![];
+    for field in &element_type.fields {
+        if let StructField::ParameterBlock(nested_block) = field {
+            let field_name =
+                rust_field_name(&nested_block.field_name, &nested_block.user_attributes);
+            let nested_resources = gather_parameter_block(
+                &nested_block.field_name,
+                &nested_block.element_type,
+                // the resources struct is shared by every block of the same type
+                &nested_block.element_type.type_name,
+                &format!("{resources_path}.{field_name}"),
+                struct_defs,
+                descriptor_sets,
+            );
+
+            // a block containing only other blocks still has their resources;
+            // only a block without any resources is left out
+            if !nested_resources.is_empty() {
+                let type_name = format!("{}Resources", nested_block.element_type.type_name);
+                push_struct_def(
+                    struct_defs,
+                    resources_struct_def(&format!("{type_name}<'a>"), &nested_resources, None),
+                );
+                param_block_resources.push(RequiredResource {
+                    field_name,
+                    resource_type: RequiredResourceType::ParameterBlock(type_name),
+                });
+            }
+
+            continue;
+        }
+
+        if let Some(generated_field) = gather_struct_defs(field, struct_defs) {
+            param_block_fields.push(generated_field);
+        };
+
+        if let Some(req) = required_resource(field) {
+            param_block_resources.push(req);
+        }
+    }
+
+    push_struct_def(
+        struct_defs,
+        GeneratedStructDefinition {
+            type_name: element_type.type_name.to_string(),
+            fields: param_block_fields,
+            gpu_write: true,
+            alignment: Some(16),
+            size: Some(element_type.size),
+            trait_derives: vec!["Debug", "Clone", "Copy", "Serialize"],
+            // a block without uniform data has no buffer to create
+            usage_traits: if element_type.size > 0 {
+                vec!["UniformData"]
+            } else {
+                vec![]
+            },
+        },
+    );
+
+    // the default-added parameter block uniform buffer,
+    // which is only present with uniform data
+    let param_name = parameter_name.to_snake_case();
+    let uniform_buffer = (element_type.size > 0).then(|| RequiredResource {
+        field_name: format!("{}_buffer", buffer_name.to_snake_case()),
+        resource_type: RequiredResourceType::UniformBuffer(element_type.type_name.clone()),
+    });
+
+    // each parameter block is its own descriptor set,
+    // with the uniform buffer at binding 0 followed by fields in declaration order
+    let handles: Vec<String> = uniform_buffer
+        .iter()
+        .chain(&param_block_resources)
+        .filter_map(|resource| raw_descriptor_handle(resource, resources_path))
+        .collect();
+    // empty descriptor sets are dropped from the reflected pipeline layout
+    if !handles.is_empty() {
+        descriptor_sets[set_index] = Some(GeneratedDescriptorSet {
+            parameter_name: param_name,
+            handles,
+        });
+    }
+
+    param_block_resources.extend(uniform_buffer);
+    param_block_resources
+}
+
+/// the handles passed to pipeline_config, for the top level or a nested ParameterBlock
+fn resources_struct_def(
+    type_name: &str,
+    required_resources: &[RequiredResource],
+    vertex_type_name: Option<&str>,
+) -> GeneratedStructDefinition {
+    let fields = required_resources
+        .iter()
+        // bindless textures and attribute samplers come from the renderer rather than a handle
+        .filter(|r| {
+            !matches!(
+                r.resource_type,
+                RequiredResourceType::BindlessTextures | RequiredResourceType::Sampler(Some(_))
+            )
+        })
+        .map(|r| {
+            let type_name = match &r.resource_type {
+                RequiredResourceType::VertexBuffer => {
+                    let vertex_type_name =
+                        vertex_type_name.expect("no struct parameter for vertex entry point");
+                    format!("Vec<{vertex_type_name}>")
+                }
+                RequiredResourceType::IndexBuffer => "Vec<u32>".to_string(),
+                RequiredResourceType::VertexCount => "u32".to_string(),
+                RequiredResourceType::InstanceBuffer(instance_type_name) => {
+                    format!("Vec<{instance_type_name}>")
+                }
+                RequiredResourceType::Texture(shape, _) => match shape {
+                    ResourceShape::Texture1D => "&'a TextureHandle<Texture1D>".to_string(),
+                    ResourceShape::Texture2D => "&'a TextureHandle".to_string(),
+                    ResourceShape::Texture3D => "&'a TextureHandle<Texture3D>".to_string(),
+                    ResourceShape::TextureCube => "&'a TextureHandle<TextureCube>".to_string(),
+                    ResourceShape::Texture2DArray => {
+                        "&'a TextureHandle<Texture2DArray>".to_string()
+                    }
+                    s => panic!("not a texture shape: {s:?}"),
+                },
+                RequiredResourceType::Sampler(None) => "&'a SamplerHandle".to_string(),
+                RequiredResourceType::UniformBuffer(element_type_name) => {
+                    format!("&'a UniformBufferHandle<{element_type_name}>")
+                }
+                RequiredResourceType::StorageBuffer(element) => {
+                    format!("&'a StorageBufferHandle<{}>", element.type_name)
+                }
+                RequiredResourceType::MutableStorageBuffer(element) => {
+                    format!("&'a MutableStorageBufferHandle<{}>", element.type_name)
+                }
+                RequiredResourceType::ParameterBlock(resources_type_name) => {
+                    format!("{resources_type_name}<'a>")
+                }
+                RequiredResourceType::BindlessTextures | RequiredResourceType::Sampler(Some(_)) => {
+                    unreachable!()
+                }
+            };
+
+            // checks the buffer's stride against the rust element type
+            let array_element = match &r.resource_type {
+                RequiredResourceType::StorageBuffer(element)
+                | RequiredResourceType::MutableStorageBuffer(element) => Some(element.clone()),
+                _ => None,
+            };
+
+            GeneratedStructFieldDefinition {
+                field_name: r.field_name.clone(),
+                visibility: "pub ",
+                type_name,
+                offset: None,
+                size: None,
+                array_element,
+                padding: false,
+            }
+        })
+        .collect();
+
+    GeneratedStructDefinition {
+        type_name: type_name.to_string(),
+        fields,
+        gpu_write: false,
+        alignment: None,
+        size: None,
+        trait_derives: vec![],
+        usage_traits: vec![],
+    }
+}
+
+/// entry point uniform parameters are passed as push constants;
+/// this generates one struct for all of them
+///
+/// slang places each entry point's push constants at offset 0, so they share the same bytes;
+/// stages can use them together by declaring the same parameters
+fn gather_push_constants(
+    reflection_json: &ReflectionJson,
+    struct_defs: &mut Vec<GeneratedStructDefinition>,
+) -> Option<GeneratedStructDefinition> {
+    let entry_points = [
+        &reflection_json.vertex_entry_point,
+        &reflection_json.fragment_entry_point,
+        &reflection_json.compute_entry_point,
+    ]
+    .into_iter()
+    .flatten();
+
+    let mut push_constant_fields = vec![];
+    for entry_point in entry_points {
+        for param in &entry_point.parameters {
+            let field = match param {
+                EntryPointParameter::Scalar(ScalarEntryPointParameter::Bound(
+                    scalar_param @ BoundScalarEntryPointParameter {
+                        binding: Binding::Uniform(binding),
+                        ..
+                    },
+                )) => GeneratedStructFieldDefinition {
+                    field_name: scalar_param.parameter_name.to_snake_case(),
+                    visibility: "pub ",
+                    type_name: scalar_type_name(scalar_param.scalar_type).to_string(),
+                    offset: Some(binding.offset),
+                    size: Some(binding.size),
+                    array_element: None,
+                    padding: false,
+                },
+
+                EntryPointParameter::Struct(
+                    struct_param @ StructEntryPointParameter {
+                        binding: Binding::Uniform(binding),
+                        ..
+                    },
+                ) => {
+                    let mut generated_fields = vec![];
+                    for field in &struct_param.fields {
+                        if let Some(generated_field) = gather_struct_defs(field, struct_defs) {
+                            generated_fields.push(generated_field);
+                        };
+                    }
+                    push_struct_def(
+                        struct_defs,
+                        GeneratedStructDefinition {
+                            type_name: struct_param.type_name.to_string(),
+                            fields: generated_fields,
+                            gpu_write: true,
+                            alignment: Some(16),
+                            size: Some(binding.size),
+                            trait_derives: vec!["Debug", "Clone", "Copy", "Serialize"],
+                            usage_traits: vec![],
+                        },
+                    );
+
+                    GeneratedStructFieldDefinition {
+                        field_name: struct_param.parameter_name.to_snake_case(),
+                        visibility: "pub ",
+                        type_name: struct_param.type_name.to_string(),
+                        offset: Some(binding.offset),
+                        size: Some(binding.size),
+                        array_element: None,
+                        padding: false,
+                    }
+                }
+
+                _ => continue,
+            };
+
+            // ie the same parameter in the vertex and fragment entry points
+            if push_constant_fields.contains(&field) {
+                continue;
+            }
+
+            let field_range = |field: &GeneratedStructFieldDefinition| {
+                let offset = field.offset.unwrap_or_default();
+                offset..offset + field.size.unwrap_or_default()
+            };
+            let overlapping = push_constant_fields.iter().find(|other| {
+                let (range, other_range) = (field_range(&field), field_range(other));
+                range.start < other_range.end && other_range.start < range.end
+            });
+            if let Some(other) = overlapping {
+                panic!(
+                    "push constants overlap between entry points: {} and {}; declare the same parameters in each",
+                    other.field_name, field.field_name
+                );
+            }
+
+            push_constant_fields.push(field);
+        }
+    }
+
+    if push_constant_fields.is_empty() {
+        return None;
+    }
+
+    let push_constants_size = reflection_json
+        .pipeline_layout
+        .push_constant_ranges
+        .iter()
+        .map(|range| range.offset + range.size)
+        .max()
+        .unwrap_or_default();
+
+    // NOTE this is padded out to a multiple of 16 bytes, so that nested structs and glam types
+    // can't add implicit padding; only the ranges' bytes are pushed (see record_push_constants)
+    Some(GeneratedStructDefinition {
+        type_name: "PushConstants".to_string(),
+        fields: push_constant_fields,
+        gpu_write: true,
+        alignment: Some(16),
+        size: Some((push_constants_size as usize).next_multiple_of(16)),
+        trait_derives: vec!["Debug", "Clone", "Copy", "Serialize"],
+        usage_traits: vec!["PushConstantData"],
+    })
 }
 
 fn gather_struct_defs(
@@ -324,112 +919,948 @@ fn gather_struct_defs(
     struct_defs: &mut Vec<GeneratedStructDefinition>,
 ) -> Option<GeneratedStructFieldDefinition> {
     match field {
-        StructField::Resource(_) => None,
+        StructField::Resource(resource) => {
+            // buffer elements aren't part of the parent struct,
+            // but still need a generated type for the handle
+            if let ResourceResultType::Struct(element_type) = &resource.result_type {
+                gather_struct_type_def(element_type, &["StorageData"], struct_defs);
+            } else if let Some(stride) = resource.element_stride {
+                let (element_type, element_size) = buffer_element_type(resource);
+                padded_element_type(
+                    element_type,
+                    element_size,
+                    stride,
+                    &["StorageData"],
+                    struct_defs,
+                );
+            }
+
+            None
+        }
+
+        StructField::Sampler(_) => None,
+        StructField::BindlessTextures(_) => None,
+
+        // nested blocks are gathered directly from their parent block; see gather_parameter_block
+        // a block in a plain struct would need its resources threaded through that struct
+        StructField::ParameterBlock(nested_block) => panic!(
+            "ParameterBlock fields are only supported directly in another ParameterBlock: {}",
+            nested_block.field_name
+        ),
 
         StructField::Scalar(scalar) => {
-            let field_type = match scalar.scalar_type {
-                ScalarType::Float32 => "f32",
-                ScalarType::Uint32 => "u32",
-            };
+            let field_type = scalar_type_name(scalar.scalar_type);
 
             Some(GeneratedStructFieldDefinition {
-                field_name: scalar.field_name.to_snake_case(),
+                field_name: rust_field_name(&scalar.field_name, &scalar.user_attributes),
+                visibility: rust_field_visibility(&scalar.user_attributes),
                 type_name: field_type.to_string(),
+                offset: uniform_offset(&scalar.binding),
+                size: uniform_size(&scalar.binding),
+                array_element: None,
+                padding: false,
             })
         }
 
         StructField::Vector(VectorStructField::Semantic(_)) => None,
         StructField::Vector(VectorStructField::Bound(vector)) => {
-            let VectorElementType::Scalar(element_type) = &vector.element_type;
-            let field_type = match (element_type.scalar_type, vector.element_count) {
-                (ScalarType::Float32, 3) => "glam::Vec3",
-                (ScalarType::Float32, 2) => "glam::Vec2",
-                (t, c) => panic!("vector not supported: type: {t:?}, count: {c}"),
+            let field_type = match user_attribute(&vector.user_attributes, "VertexFormat") {
+                Some(attribute) => packed_vertex_type_name(attribute, vector),
+                None => {
+                    let VectorElementType::Scalar(element_type) = &vector.element_type;
+                    vector_type_name(element_type.scalar_type, vector.element_count)
+                }
             };
 
             Some(GeneratedStructFieldDefinition {
-                field_name: vector.field_name.to_snake_case(),
+                field_name: rust_field_name(&vector.field_name, &vector.user_attributes),
+                visibility: rust_field_visibility(&vector.user_attributes),
                 type_name: field_type.to_string(),
+                offset: uniform_offset(&vector.binding),
+                size: uniform_size(&vector.binding),
+                array_element: None,
+                padding: false,
             })
         }
 
         StructField::Struct(struct_field) => {
-            let type_name = struct_field.struct_type.type_name.to_string();
-            let mut generated_sub_fields = vec![];
-            for sub_field in &struct_field.struct_type.fields {
-                if let Some(field_def) = gather_struct_defs(sub_field, struct_defs) {
-                    generated_sub_fields.push(field_def);
-                };
-            }
-            let sub_struct_def = GeneratedStructDefinition {
-                type_name: type_name.clone(),
-                fields: generated_sub_fields,
-                gpu_write: true,
-                trait_derives: vec!["Debug", "Clone", "Serialize"],
-            };
-            struct_defs.push(sub_struct_def);
+            gather_struct_type_def(&struct_field.struct_type, &[], struct_defs);
 
             Some(GeneratedStructFieldDefinition {
-                field_name: struct_field.field_name.to_snake_case(),
-                type_name,
+                field_name: rust_field_name(
+                    &struct_field.field_name,
+                    &struct_field.user_attributes,
+                ),
+                visibility: rust_field_visibility(&struct_field.user_attributes),
+                type_name: struct_field.struct_type.type_name.to_string(),
+                offset: uniform_offset(&struct_field.binding),
+                size: uniform_size(&struct_field.binding),
+                array_element: None,
+                padding: false,
             })
         }
 
         StructField::Matrix(matrix) => {
-            let VectorElementType::Scalar(scalar) = &matrix.element_type;
+            let Binding::Uniform(OffsetSizeBinding { size, .. }) = &matrix.binding else {
+                panic!("matrix without uniform binding: {:?}", matrix.binding);
+            };
+            let field_type = matrix_type_name(
+                &matrix.element_type,
+                matrix.row_count,
+                matrix.column_count,
+                matrix.layout,
+                *size,
+            );
+
+            Some(GeneratedStructFieldDefinition {
+                field_name: rust_field_name(&matrix.field_name, &matrix.user_attributes),
+                visibility: rust_field_visibility(&matrix.user_attributes),
+                type_name: field_type,
+                offset: uniform_offset(&matrix.binding),
+                size: uniform_size(&matrix.binding),
+                array_element: None,
+                padding: false,
+            })
+        }
+
+        StructField::Array(array) => {
+            let stride = array.element_stride;
+
+            // the size of the element's rust type, before any array padding
+            let (element_type, element_size) = match &array.element_type {
+                ArrayElementType::Scalar(scalar) => (
+                    scalar_type_name(scalar.scalar_type).to_string(),
+                    scalar_type_size(scalar.scalar_type),
+                ),
+                ArrayElementType::Vector(vector) => {
+                    let VectorElementType::Scalar(element_type) = &vector.element_type;
+                    let type_name =
+                        vector_type_name(element_type.scalar_type, vector.element_count);
+                    let size = scalar_type_size(element_type.scalar_type) * vector.element_count;
+
+                    (type_name.to_string(), size)
+                }
+                // matrices and structs are already padded to their array stride
+                ArrayElementType::Matrix(matrix) => {
+                    let type_name = matrix_type_name(
+                        &matrix.element_type,
+                        matrix.row_count,
+                        matrix.column_count,
+                        matrix.layout,
+                        matrix.size,
+                    );
+
+                    (type_name, stride)
+                }
+                ArrayElementType::Struct(struct_type) => {
+                    gather_struct_type_def(struct_type, &[], struct_defs);
 
-            let field_type = match (scalar.scalar_type, matrix.row_count, matrix.column_count) {
-                (ScalarType::Float32, 4, 4) => "glam::Mat4",
-                (s, r, c) => {
-                    panic!("matrix not supported: scalar_type: {s:?}, rows: {r}, cols: {c}")
+                    (struct_type.type_name.to_string(), stride)
                 }
             };
 
+            // ie, std140 rounds up the stride of float arrays to 16
+            let element_type =
+                padded_element_type(element_type, element_size, stride, &[], struct_defs);
+
             Some(GeneratedStructFieldDefinition {
-                field_name: matrix.field_name.to_snake_case(),
-                type_name: field_type.to_string(),
+                field_name: rust_field_name(&array.field_name, &array.user_attributes),
+                visibility: rust_field_visibility(&array.user_attributes),
+                type_name: format!("[{element_type}; {}]", array.element_count),
+                offset: uniform_offset(&array.binding),
+                // the rust array, which pads every element to the stride
+                size: uniform_offset(&array.binding).map(|_| array.element_count * stride),
+                array_element: Some(GeneratedArrayElement {
+                    type_name: element_type,
+                    stride,
+                }),
+                padding: false,
+            })
+        }
+
+        StructField::Pointer(pointer) => {
+            let value_type = match &pointer.value_type {
+                PointerValueType::Scalar(scalar) => {
+                    scalar_type_name(scalar.scalar_type).to_string()
+                }
+                PointerValueType::Vector(vector) => {
+                    let VectorElementType::Scalar(element_type) = &vector.element_type;
+                    vector_type_name(element_type.scalar_type, vector.element_count).to_string()
+                }
+                PointerValueType::Struct(struct_type) => {
+                    gather_struct_type_def(struct_type, &[], struct_defs);
+
+                    struct_type.type_name.to_string()
+                }
+                // the containing struct def is pushed by the caller
+                PointerValueType::SelfStruct(self_struct) => self_struct.type_name.to_string(),
+            };
+
+            Some(GeneratedStructFieldDefinition {
+                field_name: rust_field_name(&pointer.field_name, &pointer.user_attributes),
+                visibility: rust_field_visibility(&pointer.user_attributes),
+                type_name: format!("crate::renderer::gpu_write::GpuPtr<{value_type}>"),
+                offset: uniform_offset(&pointer.binding),
+                size: uniform_size(&pointer.binding),
+                array_element: None,
+                padding: false,
             })
         }
     }
 }
 
-fn required_resource(field: &StructField) -> Option<RequiredResource> {
-    match field {
-        StructField::Resource(res) => match res.resource_shape {
-            ResourceShape::Texture2D => Some(RequiredResource {
-                field_name: res.field_name.to_snake_case(),
-                resource_type: RequiredResourceType::Texture,
-            }),
+/// the element type for an array or buffer, with explicit padding up to the stride;
+/// a padded element gets its own struct, so that Pod is derived for its exact layout
+fn padded_element_type(
+    element_type: String,
+    size: usize,
+    stride: usize,
+    usage_traits: &[&'static str],
+    struct_defs: &mut Vec<GeneratedStructDefinition>,
+) -> String {
+    let type_name = padded_element_type_name(&element_type, size, stride);
+    if type_name == element_type {
+        return type_name;
+    }
+
+    push_struct_def(
+        struct_defs,
+        GeneratedStructDefinition {
+            type_name: type_name.clone(),
+            fields: vec![GeneratedStructFieldDefinition {
+                field_name: "value".to_string(),
+                visibility: "pub ",
+                type_name: element_type,
+                offset: Some(0),
+                size: Some(size),
+                array_element: None,
+                padding: false,
+            }],
+            gpu_write: true,
+            alignment: None,
+            size: Some(stride),
+            trait_derives: vec!["Debug", "Clone", "Copy", "Serialize"],
+            usage_traits: usage_traits.to_vec(),
         },
+    );
 
-        _ => None,
+    type_name
+}
+
+/// ie 'PaddedF32Stride16' for a float in a std140 array,
+/// or the element type itself if it fills the stride
+fn padded_element_type_name(element_type: &str, size: usize, stride: usize) -> String {
+    // NOTE 3-component vectors aren't StorageData, because std430 pads them out;
+    // with scalar layout they fill the stride, but still get a struct
+    let vector3 = matches!(
+        element_type,
+        "glam::Vec3"
+            | "glam::IVec3"
+            | "glam::UVec3"
+            | "glam::I64Vec3"
+            | "glam::U64Vec3"
+            | "glam::DVec3"
+            | "[half::f16; 3]"
+    );
+
+    match (size, stride) {
+        (size, stride) if size == stride && !vector3 => element_type.to_string(),
+        (size, stride) if size <= stride => {
+            // ie 'glam::Vec3' -> 'Vec3', or '[half::f16; 3]' -> 'F16x3'
+            let base_name = match element_type.strip_prefix('[') {
+                Some(array_type) => {
+                    let (scalar_type, count) = array_type
+                        .trim_end_matches(']')
+                        .split_once("; ")
+                        .expect("array element type without a length");
+                    format!("{}x{count}", rust_path_name(scalar_type))
+                }
+                None => rust_path_name(element_type),
+            };
+
+            let mut chars = base_name.chars();
+            let first = chars.next().expect("empty element type name");
+            format!(
+                "Padded{}{}Stride{stride}",
+                first.to_ascii_uppercase(),
+                chars.as_str()
+            )
+        }
+        (size, stride) => {
+            panic!("array stride not supported: {element_type}, size: {size}, stride: {stride}")
+        }
     }
 }
 
-#[derive(Debug)]
-struct GeneratedStructDefinition {
-    type_name: String,
-    fields: Vec<GeneratedStructFieldDefinition>,
-    gpu_write: bool,
-    trait_derives: Vec<&'static str>,
+/// the last segment of a rust path, ie 'f16' for 'half::f16'
+fn rust_path_name(path: &str) -> String {
+    path.rsplit("::").next().unwrap_or(path).to_string()
 }
 
-impl GeneratedStructDefinition {
-    fn trait_derive_line(&self) -> Option<String> {
-        if self.trait_derives.is_empty() {
+/// a structured buffer's element type and its size, before any padding to the stride
+fn buffer_element_type(res: &ResourceStructField) -> (String, usize) {
+    match &res.result_type {
+        ResourceResultType::Struct(element_type) => {
+            (element_type.type_name.clone(), element_type.size)
+        }
+        ResourceResultType::Vector(vector) => {
+            let VectorElementType::Scalar(element_type) = &vector.element_type;
+            let type_name = vector_type_name(element_type.scalar_type, vector.element_count);
+            let size = scalar_type_size(element_type.scalar_type) * vector.element_count;
+
+            (type_name.to_string(), size)
+        }
+        ResourceResultType::Scalar(scalar) => (
+            scalar_type_name(scalar.scalar_type).to_string(),
+            scalar_type_size(scalar.scalar_type),
+        ),
+    }
+}
+
+/// pushes a struct def for a nested struct, buffer element, or array element
+fn gather_struct_type_def(
+    struct_type: &StructFieldType,
+    usage_traits: &[&'static str],
+    struct_defs: &mut Vec<GeneratedStructDefinition>,
+) {
+    let mut generated_fields = vec![];
+    for field in &struct_type.fields {
+        if let Some(field_def) = gather_struct_defs(field, struct_defs) {
+            generated_fields.push(field_def);
+        };
+    }
+
+    push_struct_def(
+        struct_defs,
+        GeneratedStructDefinition {
+            type_name: struct_type.type_name.to_string(),
+            fields: generated_fields,
+            gpu_write: true,
+            // ie, 16 for std140, or the largest member's alignment for std430
+            alignment: Some(struct_type.alignment),
+            size: Some(struct_type.size),
+            trait_derives: vec!["Debug", "Clone", "Copy", "Serialize"],
+            usage_traits: usage_traits.to_vec(),
+        },
+    );
+}
+
+/// the byte offset of a field within its struct, if it has a uniform layout
+fn uniform_offset(binding: &Binding) -> Option<usize> {
+    match binding {
+        Binding::Uniform(offset_size) => Some(offset_size.offset),
+        _ => None,
+    }
+}
+
+fn uniform_size(binding: &Binding) -> Option<usize> {
+    match binding {
+        Binding::Uniform(offset_size) => Some(offset_size.size),
+        _ => None,
+    }
+}
+
+/// the same struct can be used by more than one field (or buffer) in a shader
+fn push_struct_def(
+    struct_defs: &mut Vec<GeneratedStructDefinition>,
+    struct_def: GeneratedStructDefinition,
+) {
+    // keep every usage, ie a shared struct used as a buffer element in only one shader
+    if let Some(existing_def) = struct_defs
+        .iter_mut()
+        .find(|def| def.type_name == struct_def.type_name)
+    {
+        // ie, a struct with a float3 array in both a uniform buffer (std140)
+        // and a storage buffer (std430) can't be one rust type
+        let same_layout = existing_def.fields == struct_def.fields
+            && existing_def.alignment == struct_def.alignment
+            && existing_def.size == struct_def.size;
+        if !same_layout {
+            panic!(
+                "struct used with different layouts: {}; use a separate struct for each buffer type",
+                struct_def.type_name
+            );
+        }
+
+        for usage_trait in struct_def.usage_traits {
+            if !existing_def.usage_traits.contains(&usage_trait) {
+                existing_def.usage_traits.push(usage_trait);
+            }
+        }
+
+        return;
+    }
+
+    struct_defs.push(struct_def);
+}
+
+fn scalar_type_name(scalar_type: ScalarType) -> &'static str {
+    match scalar_type {
+        // NOTE slang bools are 4 bytes in buffers
+        ScalarType::Bool => "u32",
+        ScalarType::Int32 => "i32",
+        ScalarType::Uint32 => "u32",
+        ScalarType::Int64 => "i64",
+        ScalarType::Uint64 => "u64",
+        ScalarType::Float16 => "half::f16",
+        ScalarType::Float32 => "f32",
+        ScalarType::Float64 => "f64",
+    }
+}
+
+fn scalar_type_size(scalar_type: ScalarType) -> usize {
+    match scalar_type {
+        ScalarType::Float16 => 2,
+        ScalarType::Bool | ScalarType::Int32 | ScalarType::Uint32 | ScalarType::Float32 => 4,
+        ScalarType::Int64 | ScalarType::Uint64 | ScalarType::Float64 => 8,
+    }
+}
+
+fn vector_type_name(scalar_type: ScalarType, element_count: usize) -> &'static str {
+    match (scalar_type, element_count) {
+        (ScalarType::Float32, 2) => "glam::Vec2",
+        (ScalarType::Float32, 3) => "glam::Vec3",
+        (ScalarType::Float32, 4) => "glam::Vec4",
+
+        (ScalarType::Int32, 2) => "glam::IVec2",
+        (ScalarType::Int32, 3) => "glam::IVec3",
+        (ScalarType::Int32, 4) => "glam::IVec4",
+
+        (ScalarType::Uint32 | ScalarType::Bool, 2) => "glam::UVec2",
+        (ScalarType::Uint32 | ScalarType::Bool, 3) => "glam::UVec3",
+        (ScalarType::Uint32 | ScalarType::Bool, 4) => "glam::UVec4",
+
+        (ScalarType::Int64, 2) => "glam::I64Vec2",
+        (ScalarType::Int64, 3) => "glam::I64Vec3",
+        (ScalarType::Int64, 4) => "glam::I64Vec4",
+
+        (ScalarType::Uint64, 2) => "glam::U64Vec2",
+        (ScalarType::Uint64, 3) => "glam::U64Vec3",
+        (ScalarType::Uint64, 4) => "glam::U64Vec4",
+
+        (ScalarType::Float64, 2) => "glam::DVec2",
+        (ScalarType::Float64, 3) => "glam::DVec3",
+        (ScalarType::Float64, 4) => "glam::DVec4",
+
+        // NOTE glam has no half-precision vectors
+        (ScalarType::Float16, 2) => "[half::f16; 2]",
+        (ScalarType::Float16, 3) => "[half::f16; 3]",
+        (ScalarType::Float16, 4) => "[half::f16; 4]",
+
+        (t, c) => panic!("vector not supported: type: {t:?}, count: {c}"),
+    }
+}
+
+// NOTE glam matrices are column-major;
+// with a row-major layout, they hold the transpose of the slang matrix
+fn matrix_type_name(
+    element_type: &VectorElementType,
+    row_count: u32,
+    column_count: u32,
+    layout: MatrixLayout,
+    size: usize,
+) -> String {
+    let VectorElementType::Scalar(scalar) = element_type;
+    let (rows, cols) = (row_count as usize, column_count as usize);
+    if !matches!(scalar.scalar_type, ScalarType::Float32) {
+        let s = scalar.scalar_type;
+        panic!("matrix not supported: scalar_type: {s:?}, rows: {rows}, cols: {cols}");
+    }
+
+    // the matrix is stored as an array of columns or rows
+    let (vector_count, vector_len) = match layout {
+        MatrixLayout::ColumnMajor => (cols, rows),
+        MatrixLayout::RowMajor => (rows, cols),
+    };
+
+    // slang's matrix size is either the stride times the vector count,
+    // or one less than that plus the unpadded size of the last vector
+    let vector_size = vector_len * std::mem::size_of::<f32>();
+    let stride = [vector_size, 8, 16]
+        .into_iter()
+        .filter(|stride| *stride >= vector_size)
+        .find(|stride| {
+            size == stride * vector_count || size == stride * (vector_count - 1) + vector_size
+        })
+        .unwrap_or_else(|| {
+            panic!("unexpected matrix size: {size}, rows: {rows}, cols: {cols}");
+        });
+
+    match (vector_count, vector_len, stride) {
+        (4, 4, 16) => "glam::Mat4".to_string(),
+        (3, 3, 16) => "glam::Mat3A".to_string(),
+        (3, 3, 12) => "glam::Mat3".to_string(),
+        (2, 2, 8) => "glam::Mat2".to_string(),
+
+        _ => {
+            let vector_type = match (vector_len, stride) {
+                (2, 8) => "glam::Vec2",
+                (2, 16) => "crate::renderer::gpu_write::PaddedVec2",
+                (3, 12) => "glam::Vec3",
+                (3, 16) => "glam::Vec3A",
+                (4, 16) => "glam::Vec4",
+                (l, s) => panic!("matrix vector not supported: length: {l}, stride: {s}"),
+            };
+
+            format!("[{vector_type}; {vector_count}]")
+        }
+    }
+}
+
+/// the same type without glam's 16-byte alignment, ie '[f32; 4]' for 'glam::Vec4'
+// NOTE Vec3A, Mat3A and PaddedVec2 only come from 16-byte matrix strides,
+// which scalar layout doesn't have
+fn unaligned_type_name(type_name: &str) -> String {
+    type_name
+        .replace("glam::Mat4", "[[f32; 4]; 4]")
+        .replace("glam::Vec4", "[f32; 4]")
+}
+
+/// the vertex attribute format and number of locations used by a vertex field's rust type
+/// the wrapper type for a VertexFormat attribute, ie '[VertexFormat("unorm8x4")] float4 color'
+fn packed_vertex_type_name(
+    attribute: &UserAttribute,
+    vector: &BoundVectorStructField,
+) -> &'static str {
+    let vertex_format = string_argument(attribute, 0);
+    let field_name = &vector.field_name;
+
+    if !matches!(vector.binding, Binding::VaryingInput(_)) {
+        panic!("VertexFormat attribute on a non-vertex-input field: {field_name}");
+    }
+
+    // normalized and half formats are read as floats in the shader
+    let VectorElementType::Scalar(element_type) = &vector.element_type;
+    if !matches!(
+        element_type.scalar_type,
+        ScalarType::Float32 | ScalarType::Float16
+    ) {
+        panic!("VertexFormat attribute on a non-float vector: {field_name}");
+    }
+
+    let (type_name, element_count) = match vertex_format {
+        "unorm8x2" => ("crate::renderer::vertex_format::Unorm8x2", 2),
+        "unorm8x4" => ("crate::renderer::vertex_format::Unorm8x4", 4),
+        "snorm8x2" => ("crate::renderer::vertex_format::Snorm8x2", 2),
+        "snorm8x4" => ("crate::renderer::vertex_format::Snorm8x4", 4),
+        "unorm16x2" => ("crate::renderer::vertex_format::Unorm16x2", 2),
+        "unorm16x4" => ("crate::renderer::vertex_format::Unorm16x4", 4),
+        "snorm16x2" => ("crate::renderer::vertex_format::Snorm16x2", 2),
+        "snorm16x4" => ("crate::renderer::vertex_format::Snorm16x4", 4),
+        "float16x2" => ("crate::renderer::vertex_format::Float16x2", 2),
+        "float16x4" => ("crate::renderer::vertex_format::Float16x4", 4),
+        f => panic!("unsupported VertexFormat: {f}"),
+    };
+
+    if element_count != vector.element_count {
+        panic!(
+            "VertexFormat {vertex_format} doesn't match the vector size of {field_name}: {}",
+            vector.element_count
+        );
+    }
+
+    type_name
+}
+
+/// the size and alignment in bytes of a vertex attribute's rust type
+fn vertex_attribute_layout(type_name: &str, format: &str) -> (usize, usize) {
+    // ie R64G64B64_SFLOAT has three 8-byte components
+    let (components, _) = format.split_once('_').unwrap();
+    let component_bits: Vec<usize> = components
+        .split(['R', 'G', 'B', 'A'])
+        .filter(|bits| !bits.is_empty())
+        .map(|bits| bits.parse().unwrap())
+        .collect();
+    let component_size = component_bits[0] / 8;
+
+    // NOTE glam's Vec4 is 16-byte aligned for simd
+    let alignment = if type_name == "glam::Vec4" {
+        16
+    } else {
+        component_size
+    };
+
+    (component_size * component_bits.len(), alignment)
+}
+
+fn vertex_attribute_format(type_name: &str) -> (&'static str, usize) {
+    let format = match type_name {
+        "f32" => "R32_SFLOAT",
+        "glam::Vec2" => "R32G32_SFLOAT",
+        "glam::Vec3" => "R32G32B32_SFLOAT",
+        "glam::Vec4" => "R32G32B32A32_SFLOAT",
+
+        "i32" => "R32_SINT",
+        "glam::IVec2" => "R32G32_SINT",
+        "glam::IVec3" => "R32G32B32_SINT",
+        "glam::IVec4" => "R32G32B32A32_SINT",
+
+        "u32" => "R32_UINT",
+        "glam::UVec2" => "R32G32_UINT",
+        "glam::UVec3" => "R32G32B32_UINT",
+        "glam::UVec4" => "R32G32B32A32_UINT",
+
+        "i64" => "R64_SINT",
+        "glam::I64Vec2" => "R64G64_SINT",
+        "glam::I64Vec3" => "R64G64B64_SINT",
+        "glam::I64Vec4" => "R64G64B64A64_SINT",
+
+        "u64" => "R64_UINT",
+        "glam::U64Vec2" => "R64G64_UINT",
+        "glam::U64Vec3" => "R64G64B64_UINT",
+        "glam::U64Vec4" => "R64G64B64A64_UINT",
+
+        "f64" => "R64_SFLOAT",
+        "glam::DVec2" => "R64G64_SFLOAT",
+        "glam::DVec3" => "R64G64B64_SFLOAT",
+        "glam::DVec4" => "R64G64B64A64_SFLOAT",
+
+        "half::f16" => "R16_SFLOAT",
+        "[half::f16; 2]" => "R16G16_SFLOAT",
+        "[half::f16; 3]" => "R16G16B16_SFLOAT",
+        "[half::f16; 4]" => "R16G16B16A16_SFLOAT",
+
+        // packed types from a VertexFormat attribute; see packed_vertex_type_name
+        "crate::renderer::vertex_format::Unorm8x2" => "R8G8_UNORM",
+        "crate::renderer::vertex_format::Unorm8x4" => "R8G8B8A8_UNORM",
+        "crate::renderer::vertex_format::Snorm8x2" => "R8G8_SNORM",
+        "crate::renderer::vertex_format::Snorm8x4" => "R8G8B8A8_SNORM",
+        "crate::renderer::vertex_format::Unorm16x2" => "R16G16_UNORM",
+        "crate::renderer::vertex_format::Unorm16x4" => "R16G16B16A16_UNORM",
+        "crate::renderer::vertex_format::Snorm16x2" => "R16G16_SNORM",
+        "crate::renderer::vertex_format::Snorm16x4" => "R16G16B16A16_SNORM",
+        "crate::renderer::vertex_format::Float16x2" => "R16G16_SFLOAT",
+        "crate::renderer::vertex_format::Float16x4" => "R16G16B16A16_SFLOAT",
+
+        other => panic!("vertex attribute type not supported: {other}"),
+    };
+
+    // 64-bit 3 and 4 component formats take up two locations
+    // https://registry.khronos.org/vulkan/specs/latest/html/vkspec.html#fxvertex-attrib-location
+    let location_count = match format {
+        "R64G64B64_SINT"
+        | "R64G64B64A64_SINT"
+        | "R64G64B64_UINT"
+        | "R64G64B64A64_UINT"
+        | "R64G64B64_SFLOAT"
+        | "R64G64B64A64_SFLOAT" => 2,
+        _ => 1,
+    };
+
+    (format, location_count)
+}
+
+fn required_resource(field: &StructField) -> Option<RequiredResource> {
+    match field {
+        StructField::Resource(res) => match res.resource_shape {
+            ResourceShape::Texture1D
+            | ResourceShape::Texture2D
+            | ResourceShape::Texture3D
+            | ResourceShape::TextureCube
+            | ResourceShape::Texture2DArray => {
+                let sampler_settings = sampler_settings(&res.user_attributes);
+                if sampler_settings.is_some() && !res.combined_sampler {
+                    panic!(
+                        "Sampler attribute on a separate texture: {}",
+                        res.field_name
+                    );
+                }
+
+                Some(RequiredResource {
+                    field_name: rust_field_name(&res.field_name, &res.user_attributes),
+                    resource_type: RequiredResourceType::Texture(
+                        res.resource_shape,
+                        sampler_settings,
+                    ),
+                })
+            }
+
+            ResourceShape::StructuredBuffer | ResourceShape::MutableStructuredBuffer => {
+                let (element_type, element_size) = buffer_element_type(res);
+
+                // ie, std430 rounds up the stride of float3 elements to 16;
+                // the padded element's struct def comes from gather_struct_defs
+                let stride = res
+                    .element_stride
+                    .unwrap_or_else(|| panic!("structured buffer without stride: {res:?}"));
+                let element = GeneratedArrayElement {
+                    type_name: padded_element_type_name(&element_type, element_size, stride),
+                    stride,
+                };
+
+                let resource_type = match res.resource_shape {
+                    ResourceShape::MutableStructuredBuffer => {
+                        RequiredResourceType::MutableStorageBuffer(element)
+                    }
+                    _ => RequiredResourceType::StorageBuffer(element),
+                };
+
+                Some(RequiredResource {
+                    field_name: rust_field_name(&res.field_name, &res.user_attributes),
+                    resource_type,
+                })
+            }
+        },
+
+        StructField::Sampler(sampler) => Some(RequiredResource {
+            field_name: rust_field_name(&sampler.field_name, &sampler.user_attributes),
+            resource_type: RequiredResourceType::Sampler(sampler_settings(
+                &sampler.user_attributes,
+            )),
+        }),
+
+        StructField::BindlessTextures(bindless) => Some(RequiredResource {
+            field_name: rust_field_name(&bindless.field_name, &bindless.user_attributes),
+            resource_type: RequiredResourceType::BindlessTextures,
+        }),
+
+        _ => None,
+    }
+}
+
+/// the generated field name, which a RustName attribute overrides
+fn rust_field_name(field_name: &str, attributes: &[UserAttribute]) -> String {
+    match user_attribute(attributes, "RustName") {
+        Some(attribute) => string_argument(attribute, 0).to_string(),
+        None => field_name.to_snake_case(),
+    }
+}
+
+/// the generated field visibility, which a RustVisibility attribute overrides
+fn rust_field_visibility(attributes: &[UserAttribute]) -> &'static str {
+    let Some(attribute) = user_attribute(attributes, "RustVisibility") else {
+        return "pub ";
+    };
+
+    match string_argument(attribute, 0) {
+        "pub" => "pub ",
+        "pub(crate)" => "pub(crate) ",
+        "pub(super)" => "pub(super) ",
+        "private" => "",
+        v => panic!("unsupported RustVisibility: {v}"),
+    }
+}
+
+/// the SamplerSettings expression for a Sampler attribute, if present
+fn sampler_settings(attributes: &[UserAttribute]) -> Option<String> {
+    let attribute = user_attribute(attributes, "Sampler")?;
+
+    let filter = match string_argument(attribute, 0) {
+        "nearest" => "NEAREST",
+        "linear" => "LINEAR",
+        f => panic!("unsupported Sampler filter: {f}"),
+    };
+    let address_mode = match string_argument(attribute, 1) {
+        "repeat" => "REPEAT",
+        "mirrored_repeat" => "MIRRORED_REPEAT",
+        "clamp" => "CLAMP_TO_EDGE",
+        "border" => "CLAMP_TO_BORDER",
+        a => panic!("unsupported Sampler address mode: {a}"),
+    };
+
+    Some(format!(
+        "SamplerSettings {{ filter: ash::vk::Filter::{filter}, address_mode: ash::vk::SamplerAddressMode::{address_mode} }}"
+    ))
+}
+
+fn user_attribute<'a>(attributes: &'a [UserAttribute], name: &str) -> Option<&'a UserAttribute> {
+    attributes.iter().find(|attribute| attribute.name == name)
+}
+
+fn string_argument(attribute: &UserAttribute, index: usize) -> &str {
+    match attribute.arguments.get(index) {
+        Some(UserAttributeArgument::String(argument)) => argument,
+        argument => panic!(
+            "expected a string argument {index} for {}: {argument:?}",
+            attribute.name
+        ),
+    }
+}
+
+/// the pipeline config expression for a resource bound in a descriptor set
+fn raw_descriptor_handle(resource: &RequiredResource, resources_path: &str) -> Option<String> {
+    let field = format!("{resources_path}.{}", resource.field_name);
+    let handle = match &resource.resource_type {
+        RequiredResourceType::VertexBuffer
+        | RequiredResourceType::IndexBuffer
+        | RequiredResourceType::VertexCount
+        | RequiredResourceType::InstanceBuffer(_)
+        | RequiredResourceType::ParameterBlock(_) => return None,
+        RequiredResourceType::Texture(_, None) => {
+            format!("Texture(RawTextureHandle::from_typed({field}))")
+        }
+        RequiredResourceType::Texture(_, Some(settings)) => {
+            format!("TextureWithSampler(RawTextureHandle::from_typed({field}), {settings})")
+        }
+        RequiredResourceType::Sampler(None) => {
+            format!("Sampler(RawSamplerHandle::from_handle({field}))")
+        }
+        RequiredResourceType::Sampler(Some(settings)) => format!("SamplerSettings({settings})"),
+        RequiredResourceType::UniformBuffer(_) => {
+            format!("UniformBuffer(RawUniformBufferHandle::from_typed({field}))")
+        }
+        RequiredResourceType::StorageBuffer(_) => {
+            format!("StorageBuffer(RawStorageBufferHandle::from_typed({field}))")
+        }
+        RequiredResourceType::MutableStorageBuffer(_) => {
+            format!("StorageBuffer(RawStorageBufferHandle::from_mutable({field}))")
+        }
+        RequiredResourceType::BindlessTextures => "BindlessTextures".to_string(),
+    };
+
+    Some(format!("RawDescriptorHandle::{handle}"))
+}
+
+#[derive(Debug)]
+struct GeneratedStructDefinition {
+    type_name: String,
+    fields: Vec<GeneratedStructFieldDefinition>,
+    gpu_write: bool,
+    /// the alignment for a gpu_write struct's repr, ie 16 for std140;
+    ///   otherwise it's only repr(C)
+    alignment: Option<usize>,
+    /// the reflected size in bytes, checked at compile time
+    size: Option<usize>,
+    trait_derives: Vec<&'static str>,
+    /// marker traits for where the struct is used in the shader, ie UniformData;
+    ///   these restrict which renderer apis accept the type
+    usage_traits: Vec<&'static str>,
+}
+
+impl GeneratedStructDefinition {
+    fn trait_derive_line(&self) -> Option<String> {
+        let mut trait_derives = self.trait_derives.clone();
+        // checks at compile time that there are no padding bytes
+        if self.gpu_write {
+            trait_derives.extend(["bytemuck::Pod", "bytemuck::Zeroable"]);
+        }
+
+        if trait_derives.is_empty() {
             return None;
         }
 
-        let trait_list = self.trait_derives.join(", ");
+        let trait_list = trait_derives.join(", ");
 
         Some(format!("#[derive({trait_list})]"))
     }
+
+    /// the fields with explicit padding bytes for any gaps in the reflected layout,
+    /// which are pub so that struct literals can use '..bytemuck::Zeroable::zeroed()'
+    fn padded_fields(&self) -> Vec<GeneratedStructFieldDefinition> {
+        if !self.gpu_write {
+            return self.fields.clone();
+        }
+
+        let mut padded_fields = vec![];
+        let mut padding_count = 0;
+        let mut padding_field = |byte_count: usize| {
+            let field = GeneratedStructFieldDefinition {
+                field_name: format!("_padding_{padding_count}"),
+                visibility: "pub ",
+                type_name: format!("[u8; {byte_count}]"),
+                offset: None,
+                size: None,
+                array_element: None,
+                padding: true,
+            };
+            padding_count += 1;
+
+            field
+        };
+
+        let mut end = 0;
+        for field in &self.fields {
+            if let (Some(offset), Some(size)) = (field.offset, field.size) {
+                if offset > end {
+                    padded_fields.push(padding_field(offset - end));
+                }
+                end = offset + size;
+            }
+
+            padded_fields.push(field.clone());
+        }
+
+        if let Some(size) = self.size
+            && size > end
+        {
+            padded_fields.push(padding_field(size - end));
+        }
+
+        padded_fields
+    }
+
+    /// replaces 16-byte aligned glam types and reprs,
+    /// because scalar layout only aligns to the size of each scalar
+    fn use_scalar_layout(&mut self) {
+        if !self.gpu_write || self.usage_traits.contains(&"VertexData") {
+            return;
+        }
+
+        self.alignment = None;
+        for field in &mut self.fields {
+            field.type_name = unaligned_type_name(&field.type_name);
+            if let Some(element) = &mut field.array_element {
+                element.type_name = unaligned_type_name(&element.type_name);
+            }
+        }
+    }
+
+    /// const assertions that the rust layout matches the reflected one
+    fn layout_assertions(&self) -> Option<String> {
+        let type_name = &self.type_name;
+
+        let mut assertions = vec![];
+        for field in &self.fields {
+            if let Some(offset) = field.offset {
+                let field_name = &field.field_name;
+                assertions.push(format!(
+                    "const _: () = assert!(std::mem::offset_of!({type_name}, {field_name}) == {offset});"
+                ));
+            }
+        }
+        for field in &self.fields {
+            if let Some(element) = &field.array_element {
+                let (element_type, stride) = (&element.type_name, element.stride);
+                assertions.push(format!(
+                    "const _: () = assert!(std::mem::size_of::<{element_type}>() == {stride});"
+                ));
+            }
+        }
+        if let Some(size) = self.size {
+            assertions.push(format!(
+                "const _: () = assert!(std::mem::size_of::<{type_name}>() == {size});"
+            ));
+        }
+
+        if assertions.is_empty() {
+            return None;
+        }
+
+        Some(assertions.join("\n"))
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 struct GeneratedStructFieldDefinition {
     field_name: String,
+    /// ie 'pub ', with a trailing space; empty for a private field
+    visibility: &'static str,
+    type_name: String,
+    /// the reflected byte offset, for fields with a uniform layout
+    offset: Option<usize>,
+    /// the size in bytes, for fields with a uniform layout
+    size: Option<usize>,
+    /// the element type and stride, for fixed-size array fields
+    array_element: Option<GeneratedArrayElement>,
+    /// explicit padding bytes, skipped when serializing
+    padding: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct GeneratedArrayElement {
     type_name: String,
+    /// the reflected array stride in bytes
+    stride: usize,
 }
 
 struct GeneratedFile {
@@ -449,6 +1880,10 @@ fn write_generated_file(config: &Config, source_file: &GeneratedFile) -> anyhow:
 
 struct VertexImplBlock {
     type_name: String,
+    /// the vertex buffer binding; 1 for per-instance data
+    binding: u32,
+    /// the VertexInputRate variant
+    input_rate: &'static str,
     attribute_descriptions: Vec<VertexAttributeDescription>,
 }
 
@@ -463,17 +1898,40 @@ struct RequiredResource {
     resource_type: RequiredResourceType,
 }
 
+/// the resource handles for one ParameterBlock's descriptor set
+struct GeneratedDescriptorSet {
+    parameter_name: String,
+    /// RawDescriptorHandle expressions, in binding order
+    handles: Vec<String>,
+}
+
 enum RequiredResourceType {
     VertexBuffer,
     IndexBuffer,
-    Texture,
+    /// the number of vertices for a non-indexed draw, without vertex or index buffers
+    VertexCount,
+    /// the vertex buffer for a [PerInstance] parameter, with its element type
+    InstanceBuffer(String),
+    /// with the SamplerSettings expression from a Sampler attribute
+    Texture(ResourceShape, Option<String>),
+    /// with the SamplerSettings expression from a Sampler attribute,
+    /// which replaces the SamplerHandle
+    Sampler(Option<String>),
     UniformBuffer(String),
+    /// with the element type, padded to the buffer's stride
+    StorageBuffer(GeneratedArrayElement),
+    MutableStorageBuffer(GeneratedArrayElement),
+    /// the generated resources struct for a nested ParameterBlock
+    ParameterBlock(String),
+    /// all textures, at their bindless indices
+    BindlessTextures,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::shaders::SCALAR_BLOCK_LAYOUT;
     use crate::util::manifest_path;
 
     #[test]
@@ -486,6 +1944,7 @@ mod tests {
             rust_source_dir: tmp_dir_path.join("src"),
             shaders_source_dir: manifest_path(["shaders", "source"]),
             compiled_shaders_dir: tmp_dir_path.join(relative_path(["shaders", "compiled"])),
+            scalar_block_layout: SCALAR_BLOCK_LAYOUT,
         };
 
         write_precompiled_shaders(config).unwrap();
@@ -504,4 +1963,354 @@ mod tests {
             });
         });
     }
+
+    /// the generated layout assertions for this fixture should fail to compile,
+    /// because its scalar-layout buffer element can't be matched by glam types
+    #[test]
+    fn bad_layout_fixture() {
+        let content = generated_fixture("bad_layout", false);
+
+        // a field's offset is always a multiple of its alignment,
+        // so no rust layout can satisfy the generated assertion
+        assert!(content.contains("pub color: glam::Vec4,"));
+        assert!(content.contains("assert!(std::mem::offset_of!(Particle, color) == 4);"));
+        assert_ne!(4 % std::mem::align_of::<glam::Vec4>(), 0);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// a ParameterBlock nested in another should get its own descriptor set and resources
+    #[test]
+    fn nested_blocks_fixture() {
+        let content = generated_fixture("nested_blocks", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// 64-bit, 16-bit and bool fields, in a uniform buffer and as vertex attributes
+    #[test]
+    fn scalar_types_fixture() {
+        let content = generated_fixture("scalar_types", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// vectors and matrices at offsets that glam's aligned types can't have
+    #[test]
+    fn scalar_layout_fixture() {
+        let content = generated_fixture("scalar_layout", true);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// entry point uniform parameters, gathered into one PushConstants struct
+    #[test]
+    fn push_constants_fixture() {
+        let content = generated_fixture("push_constants", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// a compute entry point, with mutable storage buffers and push constants
+    #[test]
+    fn compute_fixture() {
+        let content = generated_fixture("particles", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// read-only storage buffers of structs, vectors and scalars, laid out with their element stride
+    #[test]
+    fn storage_buffers_fixture() {
+        let content = generated_fixture("storage_buffers", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// read-write storage buffers of structs and scalars, as typed handles that can be read back
+    #[test]
+    fn rw_storage_buffers_fixture() {
+        let content = generated_fixture("rw_storage_buffers", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// a depth-only pipeline without a fragment entry point
+    #[test]
+    fn depth_only_fixture() {
+        let content = generated_fixture("shadow_map", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// non-4x4 matrices, with each column (or row, for row_major) padded to 16 bytes
+    #[test]
+    fn matrices_fixture() {
+        let content = generated_fixture("matrices", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// arrays of scalars, vectors, structs and matrices, padded to their uniform buffer stride
+    #[test]
+    fn arrays_fixture() {
+        let content = generated_fixture("arrays", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// 1D, 3D, cube and 2D array textures, each with its own handle type
+    #[test]
+    fn texture_shapes_fixture() {
+        let content = generated_fixture("texture_shapes", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// separate textures, and one sampler shared between them
+    #[test]
+    fn separate_samplers_fixture() {
+        let content = generated_fixture("separate_samplers", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// each global ParameterBlock should get its own descriptor set and resources
+    #[test]
+    fn multiple_blocks_fixture() {
+        let content = generated_fixture("multiple_blocks", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// an unbounded texture array, bound as the bindless texture table
+    #[test]
+    fn bindless_fixture() {
+        let content = generated_fixture("bindless", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// pointers to structs, vectors and scalars, and a struct that points to itself
+    #[test]
+    fn pointers_fixture() {
+        let content = generated_fixture("pointers", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// specialization constants, with a typed setter for each
+    #[test]
+    fn specialization_fixture() {
+        let content = generated_fixture("specialization", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// a link-time specialized variant, declared in a variants.json sidecar
+    #[test]
+    fn variants_fixture() {
+        let content = generated_fixture("lighting", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// RustName, RustVisibility and Sampler attributes on ParameterBlock fields
+    #[test]
+    fn user_attributes_fixture() {
+        let content = generated_fixture("user_attributes", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// a [PerInstance] vertex parameter, as a second vertex buffer binding
+    #[test]
+    fn instancing_fixture() {
+        let content = generated_fixture("instancing", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// a pipeline without vertex buffers, drawn from SV_VertexID
+    #[test]
+    fn bufferless_fixture() {
+        let content = generated_fixture("fullscreen", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// VertexFormat attributes, as packed vertex attribute formats
+    #[test]
+    fn packed_vertex_formats_fixture() {
+        let content = generated_fixture("packed_vertex_formats", false);
+
+        insta::assert_snapshot!(content);
+    }
+
+    /// a float array has a 16 byte stride in a uniform buffer, but not a storage buffer
+    #[test]
+    #[should_panic(expected = "struct used with different layouts: Weights")]
+    fn conflicting_struct_layouts() {
+        let weights_type = |stride: usize, alignment: usize| -> StructFieldType {
+            serde_json::from_value(serde_json::json!({
+                "typeName": "Weights",
+                "fields": [{
+                    "kind": "array",
+                    "fieldName": "weights",
+                    "binding": { "kind": "uniform", "offset": 0, "size": stride * 2 },
+                    "elementCount": 2,
+                    "elementStride": stride,
+                    "elementType": { "kind": "scalar", "scalarType": "float32" },
+                }],
+                "size": stride * 2,
+                "alignment": alignment,
+            }))
+            .unwrap()
+        };
+
+        let mut struct_defs = vec![];
+        gather_struct_type_def(&weights_type(16, 16), &[], &mut struct_defs);
+        gather_struct_type_def(&weights_type(4, 4), &["StorageData"], &mut struct_defs);
+    }
+
+    /// each stage's push constants start at offset 0, so different parameters would share bytes
+    #[test]
+    #[should_panic(expected = "push constants overlap between entry points: time and tint")]
+    fn overlapping_push_constants() {
+        let entry_point = |name: &str, stage: &str, parameter_name: &str| {
+            serde_json::json!({
+                "entryPointName": name,
+                "stage": stage,
+                "parameters": [{
+                    "kind": "scalar",
+                    "parameterName": parameter_name,
+                    "binding": { "kind": "uniform", "offset": 0, "size": 4 },
+                    "scalarType": "float32",
+                }],
+            })
+        };
+        let reflection_json: ReflectionJson = serde_json::from_value(serde_json::json!({
+            "sourceFileName": "overlapping.shader.slang",
+            "globalParameters": [],
+            "vertexEntryPoint": entry_point("vertexMain", "vertex", "time"),
+            "fragmentEntryPoint": entry_point("fragmentMain", "fragment", "tint"),
+            "computeEntryPoint": null,
+            "pipelineLayout": {
+                "descriptorSetLayouts": [],
+                "pushConstantRanges": [
+                    { "stageFlags": "vertex", "offset": 0, "size": 4 },
+                    { "stageFlags": "fragment", "offset": 0, "size": 4 },
+                ],
+            },
+        }))
+        .unwrap();
+
+        gather_push_constants(&reflection_json, &mut vec![]);
+    }
+
+    /// padded elements are generated structs, so derive(Pod) checks their exact layout,
+    /// and the generated assertions fail to compile if the size isn't the stride
+    #[test]
+    fn padded_element_layouts() {
+        let padded_elements = [
+            // ie float3 in a std430 buffer, or a float in a std140 array
+            ("glam::Vec3", 12, 16, "PaddedVec3Stride16"),
+            ("f32", 4, 16, "PaddedF32Stride16"),
+            ("[half::f16; 3]", 6, 8, "PaddedF16x3Stride8"),
+            ("glam::DVec3", 24, 32, "PaddedDVec3Stride32"),
+            // more padding than the element itself
+            ("f32", 4, 48, "PaddedF32Stride48"),
+            ("glam::Vec4", 16, 64, "PaddedVec4Stride64"),
+        ];
+
+        for (element_type, size, stride, expected_type_name) in padded_elements {
+            let mut struct_defs = vec![];
+            let type_name = padded_element_type(
+                element_type.to_string(),
+                size,
+                stride,
+                &["StorageData"],
+                &mut struct_defs,
+            );
+            assert_eq!(type_name, expected_type_name);
+
+            let [def] = struct_defs.as_slice() else {
+                panic!("expected one struct def for {element_type}: {struct_defs:?}");
+            };
+            assert_eq!(def.type_name, type_name);
+            assert_eq!(def.alignment, None);
+
+            let fields = def.padded_fields();
+            let field_types: Vec<&str> = fields.iter().map(|f| f.type_name.as_str()).collect();
+            let padding_type = format!("[u8; {}]", stride - size);
+            assert_eq!(field_types, [element_type, padding_type.as_str()]);
+
+            let assertions = def.layout_assertions().unwrap();
+            let size_assertion =
+                format!("const _: () = assert!(std::mem::size_of::<{type_name}>() == {stride});");
+            assert!(assertions.contains(&size_assertion), "{assertions}");
+        }
+
+        // an element that fills its stride isn't wrapped
+        let mut struct_defs = vec![];
+        let type_name =
+            padded_element_type("glam::Vec4".to_string(), 16, 16, &[], &mut struct_defs);
+        assert_eq!(type_name, "glam::Vec4");
+        assert!(struct_defs.is_empty());
+
+        // unless it's a 3-component vector, ie float3 in a buffer with scalar layout
+        let type_name = padded_element_type(
+            "glam::Vec3".to_string(),
+            12,
+            12,
+            &["StorageData"],
+            &mut struct_defs,
+        );
+        assert_eq!(type_name, "PaddedVec3Stride12");
+        assert_eq!(struct_defs[0].padded_fields().len(), 1);
+    }
+
+    /// generates rust source for one shader in 'shaders/fixtures', ie "bad_layout",
+    /// and returns its generated module
+    fn generated_fixture(shader_name: &str, scalar_block_layout: bool) -> String {
+        let tmp_prefix = format!("shader-test-{}", uuid::Uuid::new_v4());
+        let tmp_dir_path = std::env::temp_dir().join(tmp_prefix);
+
+        // only the shader under test is copied, so one broken fixture can't fail every test;
+        // its variants file and the plain slang modules it might import come along too
+        let fixtures_dir = manifest_path(["shaders", "fixtures"]);
+        let shaders_source_dir = tmp_dir_path.join(relative_path(["shaders", "fixtures"]));
+        std::fs::create_dir_all(&shaders_source_dir).unwrap();
+
+        let shader_file_name = format!("{shader_name}{SHADER_FILE_SUFFIX}");
+        for entry in std::fs::read_dir(&fixtures_dir).unwrap() {
+            let file_name = entry.unwrap().file_name().into_string().unwrap();
+            if file_name.ends_with(SHADER_FILE_SUFFIX) && file_name != shader_file_name {
+                continue;
+            }
+
+            let from = fixtures_dir.join(&file_name);
+            std::fs::copy(from, shaders_source_dir.join(&file_name)).unwrap();
+        }
+
+        let config = Config {
+            generate_rust_source: true,
+            rust_source_dir: tmp_dir_path.join("src"),
+            shaders_source_dir,
+            compiled_shaders_dir: tmp_dir_path.join(relative_path(["shaders", "compiled"])),
+            scalar_block_layout,
+        };
+
+        write_precompiled_shaders(config).unwrap();
+
+        // one module, named after the shader or its only variant
+        let generated_dir = tmp_dir_path.join(relative_path(["src", "generated", "shader_atlas"]));
+        let generated_paths: Vec<_> = std::fs::read_dir(generated_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(generated_paths.len(), 1, "{generated_paths:?}");
+
+        std::fs::read_to_string(&generated_paths[0]).unwrap()
+    }
 }
@@ -13,9 +13,38 @@ pub use pipeline_builders::*;
 pub struct ReflectionJson {
     pub source_file_name: String,
     pub global_parameters: Vec<GlobalParameter>,
-    pub vertex_entry_point: EntryPoint,
-    pub fragment_entry_point: EntryPoint,
+    // NOTE these are either vertex with an optional fragment, or compute only
+    pub vertex_entry_point: Option<EntryPoint>,
+    pub fragment_entry_point: Option<EntryPoint>,
+    pub compute_entry_point: Option<EntryPoint>,
     pub pipeline_layout: ReflectedPipelineLayout,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub specialization_constants: Vec<SpecializationConstant>,
+    /// the module linked in to resolve the shader's extern types, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<ShaderVariant>,
+    /// structs declared in imported non-shader modules
+    #[serde(default)]
+    pub imported_types: Vec<ImportedType>,
+}
+
+/// one entry in a shader's sidecar variants file, ie 'lit.variants.json'
+///
+/// the variant module provides the concrete types for the shader's extern declarations,
+/// ie 'export struct Material : IMaterial = PhongMaterial;'
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShaderVariant {
+    /// appended to the shader name for the generated module and compiled files
+    pub variant_name: String,
+    pub module_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedType {
+    pub type_name: String,
+    pub module_name: String,
 }
 
 impl ReflectionJson {
@@ -27,7 +56,10 @@ impl ReflectionJson {
             .map(|dsl| {
                 use ash::vk;
 
-                use crate::renderer::{TextureDescription, UniformBufferDescription};
+                use crate::renderer::{
+                    BINDLESS_TEXTURE_CAPACITY, BindlessTextureDescription, SamplerDescription,
+                    StorageBufferDescription, TextureDescription, UniformBufferDescription,
+                };
                 use crate::shaders::json::ReflectedBindingType;
 
                 // NOTE this depends on the order from 'pipeline_config'
@@ -35,6 +67,25 @@ impl ReflectionJson {
                 dsl.binding_ranges
                     .iter()
                     .map(|b| match b.descriptor_type {
+                        ReflectedBindingType::CombinedTextureSampler
+                        | ReflectedBindingType::Texture
+                            if b.unbounded =>
+                        {
+                            let descriptor_type = match b.descriptor_type {
+                                ReflectedBindingType::CombinedTextureSampler => {
+                                    vk::DescriptorType::COMBINED_IMAGE_SAMPLER
+                                }
+                                _ => vk::DescriptorType::SAMPLED_IMAGE,
+                            };
+
+                            LayoutDescription::BindlessTextures(BindlessTextureDescription {
+                                layout: vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                                binding: b.binding,
+                                descriptor_type,
+                                descriptor_count: BINDLESS_TEXTURE_CAPACITY,
+                            })
+                        }
+
                         ReflectedBindingType::ConstantBuffer => {
                             LayoutDescription::Uniform(UniformBufferDescription {
                                 size: b.size as u64,
@@ -51,7 +102,34 @@ impl ReflectionJson {
                             })
                         }
 
-                        b => todo!("unhandled binding type: {b:?}"),
+                        ReflectedBindingType::Texture => {
+                            LayoutDescription::SampledTexture(TextureDescription {
+                                layout: vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                                binding: b.binding,
+                                descriptor_count: 1,
+                            })
+                        }
+
+                        ReflectedBindingType::Sampler => {
+                            LayoutDescription::Sampler(SamplerDescription {
+                                binding: b.binding,
+                                descriptor_count: 1,
+                            })
+                        }
+
+                        ReflectedBindingType::StorageBuffer => {
+                            LayoutDescription::StorageBuffer(StorageBufferDescription {
+                                binding: b.binding,
+                                descriptor_count: 1,
+                            })
+                        }
+
+                        ReflectedBindingType::StorageTexelBuffer => {
+                            LayoutDescription::StorageTexelBuffer(StorageBufferDescription {
+                                binding: b.binding,
+                                descriptor_count: 1,
+                            })
+                        }
                     })
                     .collect()
             })
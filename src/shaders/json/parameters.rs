@@ -22,6 +22,35 @@ pub struct ParameterBlockGlobalParameter {
 pub struct ParameterBlockElementType {
     pub type_name: String,
     pub fields: Vec<StructField>,
+    /// the size of the uniform data in bytes
+    pub size: usize,
+}
+
+/// a `[SpecializationConstant]` or `[vk::constant_id(n)]` global
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecializationConstant {
+    pub constant_name: String,
+    pub constant_id: u32,
+    pub scalar_type: ScalarType,
+}
+
+/// a user-defined slang attribute, ie '[RustName("albedo")]'
+///
+/// these are declared in shaders/source/attributes.slang, and interpreted by build_tasks
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAttribute {
+    pub name: String,
+    pub arguments: Vec<UserAttributeArgument>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum UserAttributeArgument {
+    Int(i32),
+    Float(f32),
+    String(String),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -30,6 +59,9 @@ pub struct EntryPoint {
     pub entry_point_name: String,
     pub stage: EntryPointStage,
     pub parameters: Vec<EntryPointParameter>,
+    /// the numthreads attribute, for compute entry points
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_group_size: Option<[u32; 3]>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -45,6 +77,7 @@ pub enum EntryPointStage {
 pub enum EntryPointParameter {
     Struct(StructEntryPointParameter),
     Scalar(ScalarEntryPointParameter),
+    Vector(VectorEntryPointParameter),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -54,6 +87,8 @@ pub struct StructEntryPointParameter {
     pub binding: Binding,
     pub type_name: String,
     pub fields: Vec<StructField>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_attributes: Vec<UserAttribute>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -79,6 +114,31 @@ pub struct SemanticScalarEntryPointParameter {
     pub scalar_type: ScalarType,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged, rename_all = "camelCase")]
+pub enum VectorEntryPointParameter {
+    Bound(BoundVectorEntryPointParameter),
+    Semantic(SemanticVectorEntryPointParameter),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoundVectorEntryPointParameter {
+    pub parameter_name: String,
+    pub binding: Binding,
+    pub element_count: usize,
+    pub element_type: VectorElementType,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticVectorEntryPointParameter {
+    pub parameter_name: String,
+    pub semantic_name: String,
+    pub element_count: usize,
+    pub element_type: VectorElementType,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "camelCase")]
 pub enum StructField {
@@ -87,6 +147,11 @@ pub enum StructField {
     Struct(StructStructField),
     Matrix(MatrixStructField),
     Resource(ResourceStructField),
+    Array(ArrayStructField),
+    Sampler(SamplerStructField),
+    ParameterBlock(ParameterBlockStructField),
+    BindlessTextures(BindlessTexturesStructField),
+    Pointer(PointerStructField),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -139,6 +204,8 @@ pub struct ScalarStructField {
     pub field_name: String,
     pub binding: Binding,
     pub scalar_type: ScalarType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_attributes: Vec<UserAttribute>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -148,6 +215,8 @@ pub struct BoundVectorStructField {
     pub binding: Binding,
     pub element_count: usize,
     pub element_type: VectorElementType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_attributes: Vec<UserAttribute>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -158,6 +227,49 @@ pub struct MatrixStructField {
     pub row_count: u32,
     pub column_count: u32,
     pub element_type: VectorElementType,
+    pub layout: MatrixLayout,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_attributes: Vec<UserAttribute>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArrayStructField {
+    pub field_name: String,
+    pub binding: Binding,
+    pub element_count: usize,
+    /// the distance between elements in bytes, using the containing buffer's layout rules
+    pub element_stride: usize,
+    pub element_type: ArrayElementType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_attributes: Vec<UserAttribute>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ArrayElementType {
+    Scalar(ScalarVectorElementType),
+    Vector(VectorResultType),
+    Matrix(MatrixArrayElementType),
+    Struct(StructFieldType),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatrixArrayElementType {
+    pub row_count: u32,
+    pub column_count: u32,
+    pub element_type: VectorElementType,
+    pub layout: MatrixLayout,
+    /// the size of one element in bytes, not including trailing array padding
+    pub size: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum MatrixLayout {
+    RowMajor,
+    ColumnMajor,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -167,18 +279,95 @@ pub struct ResourceStructField {
     pub binding: Binding,
     pub resource_shape: ResourceShape,
     pub result_type: ResourceResultType,
+    /// the distance between elements in bytes, for structured buffers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub element_stride: Option<usize>,
+    /// whether a texture includes its sampler, ie Sampler2D rather than Texture2D
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub combined_sampler: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_attributes: Vec<UserAttribute>,
+}
+
+/// a separate SamplerState
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SamplerStructField {
+    pub field_name: String,
+    pub binding: Binding,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_attributes: Vec<UserAttribute>,
+}
+
+/// an unbounded Texture2D[] or Sampler2D[], filled from the renderer's bindless table
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BindlessTexturesStructField {
+    pub field_name: String,
+    pub binding: Binding,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub combined_sampler: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_attributes: Vec<UserAttribute>,
+}
+
+/// a buffer device address, ie `T*`
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PointerStructField {
+    pub field_name: String,
+    pub binding: Binding,
+    pub value_type: PointerValueType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_attributes: Vec<UserAttribute>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum PointerValueType {
+    Scalar(ScalarVectorElementType),
+    Vector(VectorResultType),
+    Struct(StructFieldType),
+    /// a pointer to the containing struct, ie a linked list node
+    // NOTE this doesn't handle mutually recursive structs
+    SelfStruct(SelfStructValueType),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfStructValueType {
+    pub type_name: String,
 }
 
+/// a ParameterBlock nested in another ParameterBlock, with its own descriptor set
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct ParameterBlockStructField {
+    pub field_name: String,
+    pub element_type: ParameterBlockElementType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_attributes: Vec<UserAttribute>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
 pub enum ResourceShape {
+    Texture1D,
     Texture2D,
+    Texture3D,
+    TextureCube,
+    Texture2DArray,
+    StructuredBuffer,
+    /// a RWStructuredBuffer
+    MutableStructuredBuffer,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "camelCase")]
 pub enum ResourceResultType {
+    Scalar(ScalarVectorElementType),
     Vector(VectorResultType),
+    Struct(StructFieldType),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -194,6 +383,8 @@ pub struct StructStructField {
     pub field_name: String,
     pub binding: Binding,
     pub struct_type: StructFieldType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_attributes: Vec<UserAttribute>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -201,6 +392,10 @@ pub struct StructStructField {
 pub struct StructFieldType {
     pub type_name: String,
     pub fields: Vec<StructField>,
+    /// the size in bytes, using the containing buffer's layout rules
+    pub size: usize,
+    /// the alignment in bytes, using the containing buffer's layout rules
+    pub alignment: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -218,6 +413,12 @@ pub struct ScalarVectorElementType {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 pub enum ScalarType {
-    Float32,
+    Bool,
+    Int32,
     Uint32,
+    Int64,
+    Uint64,
+    Float16,
+    Float32,
+    Float64,
 }
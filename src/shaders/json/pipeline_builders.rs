@@ -27,6 +27,9 @@ pub struct ReflectedDescriptorSetLayoutBinding {
     pub binding: u32,
     pub descriptor_type: ReflectedBindingType,
     pub descriptor_count: u32,
+    /// an unbounded array, sized by the renderer's bindless table
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unbounded: bool,
     pub stage_flags: ReflectedStageFlags,
     pub size: usize,
 }
@@ -47,6 +50,8 @@ pub enum ReflectedBindingType {
     Texture,
     ConstantBuffer,
     CombinedTextureSampler,
+    StorageBuffer,
+    StorageTexelBuffer,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
@@ -11,6 +11,8 @@ use pipeline_layout::*;
 pub fn reflection_json(
     source_file_name: &str,
     program_layout: &slang::reflection::Shader,
+    imported_types: Vec<ImportedType>,
+    variant: Option<ShaderVariant>,
 ) -> anyhow::Result<ReflectionJson> {
     let parameters = reflect_entry_points(program_layout)?;
 
@@ -21,7 +23,11 @@ pub fn reflection_json(
         global_parameters: parameters.global_parameters,
         vertex_entry_point: parameters.entry_points.vertex_entry_point,
         fragment_entry_point: parameters.entry_points.fragment_entry_point,
+        compute_entry_point: parameters.entry_points.compute_entry_point,
         pipeline_layout,
+        specialization_constants: parameters.specialization_constants,
+        variant,
+        imported_types,
     };
 
     Ok(reflection_json)
@@ -7,12 +7,15 @@ use crate::shaders::json::*;
 
 pub struct Parameters {
     pub global_parameters: Vec<GlobalParameter>,
-    pub entry_points: VertFragEntryPoints,
+    pub specialization_constants: Vec<SpecializationConstant>,
+    pub entry_points: ShaderEntryPoints,
 }
 
-pub struct VertFragEntryPoints {
-    pub vertex_entry_point: EntryPoint,
-    pub fragment_entry_point: EntryPoint,
+/// either vertex with an optional fragment, or compute only
+pub struct ShaderEntryPoints {
+    pub vertex_entry_point: Option<EntryPoint>,
+    pub fragment_entry_point: Option<EntryPoint>,
+    pub compute_entry_point: Option<EntryPoint>,
 }
 
 pub fn reflect_entry_points(
@@ -20,32 +23,35 @@ pub fn reflect_entry_points(
 ) -> anyhow::Result<Parameters> {
     let mut vertex_entry_point: Option<EntryPoint> = None;
     let mut fragment_entry_point: Option<EntryPoint> = None;
+    let mut compute_entry_point: Option<EntryPoint> = None;
 
     let mut global_parameters: Vec<GlobalParameter> = vec![];
+    let mut specialization_constants: Vec<SpecializationConstant> = vec![];
     for global_param in program_layout.parameters() {
         let parameter_name = global_param.name().unwrap().to_string();
 
+        if global_param.category() == slang::ParameterCategory::SpecializationConstant {
+            let Some(slang_scalar_type) = global_param.type_layout().scalar_type() else {
+                anyhow::bail!("non-scalar specialization constant: {parameter_name}");
+            };
+
+            specialization_constants.push(SpecializationConstant {
+                constant_name: parameter_name,
+                constant_id: global_param.offset(slang::ParameterCategory::SpecializationConstant)
+                    as u32,
+                scalar_type: scalar_from_slang(slang_scalar_type),
+            });
+
+            continue;
+        }
+
         if global_param.type_layout().kind() != slang::TypeKind::ParameterBlock {
             anyhow::bail!(
                 "non-ParameterBlock global: {parameter_name}; only ParameterBlock globals are supported"
             )
         }
 
-        let element_type_layout = global_param.type_layout().element_type_layout();
-
-        let element_type = match element_type_layout.kind() {
-            slang::TypeKind::Struct => {
-                let element_type_name = element_type_layout.name().unwrap().to_string();
-                let fields = reflect_struct_fields(element_type_layout)?;
-
-                ParameterBlockElementType {
-                    type_name: element_type_name,
-                    fields,
-                }
-            }
-
-            k => unimplemented!("type kind reflection not implemented: {k:?}"),
-        };
+        let element_type = reflect_parameter_block_element(global_param.type_layout())?;
 
         let parameter_block = ParameterBlockGlobalParameter {
             parameter_name,
@@ -75,6 +81,7 @@ pub fn reflect_entry_points(
                         binding: param_binding(param).unwrap(),
                         type_name,
                         fields,
+                        user_attributes: reflect_user_attributes(param),
                     })
                 }
 
@@ -104,6 +111,38 @@ pub fn reflect_entry_points(
                     EntryPointParameter::Scalar(scalar_param)
                 }
 
+                slang::TypeKind::Vector => {
+                    let semantic = param.semantic_name().map(str::to_string);
+                    let element_count = type_layout.element_count().unwrap();
+                    let scalar_type =
+                        scalar_from_slang(type_layout.element_type_layout().scalar_type().unwrap());
+                    let element_type =
+                        VectorElementType::Scalar(ScalarVectorElementType { scalar_type });
+
+                    let vector_param = match semantic {
+                        Some(semantic_name) => {
+                            VectorEntryPointParameter::Semantic(SemanticVectorEntryPointParameter {
+                                parameter_name,
+                                semantic_name,
+                                element_count,
+                                element_type,
+                            })
+                        }
+
+                        None => {
+                            let binding = param_binding(param).unwrap();
+                            VectorEntryPointParameter::Bound(BoundVectorEntryPointParameter {
+                                parameter_name,
+                                binding,
+                                element_count,
+                                element_type,
+                            })
+                        }
+                    };
+
+                    EntryPointParameter::Vector(vector_param)
+                }
+
                 k => todo!("type kind reflection not implemented: {k:?}"),
             };
 
@@ -116,6 +155,7 @@ pub fn reflect_entry_points(
                     entry_point_name,
                     stage: EntryPointStage::Vertex,
                     parameters: params,
+                    thread_group_size: None,
                 });
             }
 
@@ -124,32 +164,72 @@ pub fn reflect_entry_points(
                     entry_point_name,
                     stage: EntryPointStage::Fragment,
                     parameters: params,
+                    thread_group_size: None,
                 });
             }
 
-            _ => todo!(),
+            slang::Stage::Compute => {
+                let thread_group_size = entry_point.compute_thread_group_size().map(|s| s as u32);
+
+                compute_entry_point = Some(EntryPoint {
+                    entry_point_name,
+                    stage: EntryPointStage::Compute,
+                    parameters: params,
+                    thread_group_size: Some(thread_group_size),
+                });
+            }
+
+            s => todo!("entry point stage not handled: {s:?}"),
         }
     }
 
-    let (vertex_entry_point, fragment_entry_point) =
-        match (vertex_entry_point, fragment_entry_point) {
-            (Some(v), Some(f)) => (v, f),
-            _ => anyhow::bail!("failed to load vertex and fragment entry points"),
-        };
+    match (
+        &vertex_entry_point,
+        &fragment_entry_point,
+        &compute_entry_point,
+    ) {
+        (Some(_), _, None) | (None, None, Some(_)) => {}
+        _ => anyhow::bail!(
+            "failed to load entry points; expected either vertex (and optionally fragment), or compute only"
+        ),
+    }
 
-    let entry_points = VertFragEntryPoints {
+    let entry_points = ShaderEntryPoints {
         vertex_entry_point,
         fragment_entry_point,
+        compute_entry_point,
     };
 
     let parameters = Parameters {
         global_parameters,
+        specialization_constants,
         entry_points,
     };
 
     Ok(parameters)
 }
 
+fn reflect_parameter_block_element(
+    parameter_block_type_layout: &slang::reflection::TypeLayout,
+) -> anyhow::Result<ParameterBlockElementType> {
+    let element_type_layout = parameter_block_type_layout.element_type_layout();
+
+    match element_type_layout.kind() {
+        slang::TypeKind::Struct => {
+            let element_type_name = element_type_layout.name().unwrap().to_string();
+            let fields = reflect_struct_fields(element_type_layout)?;
+
+            Ok(ParameterBlockElementType {
+                type_name: element_type_name,
+                fields,
+                size: element_type_layout.size(slang::ParameterCategory::Uniform),
+            })
+        }
+
+        k => unimplemented!("type kind reflection not implemented: {k:?}"),
+    }
+}
+
 fn reflect_struct_fields(
     struct_type_layout: &slang::reflection::TypeLayout,
 ) -> anyhow::Result<Vec<StructField>> {
@@ -159,6 +239,7 @@ fn reflect_struct_fields(
         let field_name = field.name().unwrap().to_string();
         let field_semantic_name = field.semantic_name().map(str::to_string);
         let field_type_layout = field.type_layout();
+        let user_attributes = reflect_user_attributes(field);
 
         // TODO handle this being optional in a better way; avoid the unwraps() below
         let binding = param_binding(field);
@@ -172,6 +253,7 @@ fn reflect_struct_fields(
                     field_name,
                     binding: binding.unwrap(),
                     scalar_type,
+                    user_attributes,
                 })
             }
 
@@ -201,6 +283,7 @@ fn reflect_struct_fields(
                             binding: field_binding,
                             element_count: vec_elem_count,
                             element_type: vec_elem_type,
+                            user_attributes,
                         })
                     }
 
@@ -230,6 +313,112 @@ fn reflect_struct_fields(
                     row_count,
                     column_count,
                     element_type,
+                    layout: matrix_layout_from_slang(field_type_layout),
+                    user_attributes,
+                })
+            }
+
+            slang::TypeKind::Array
+                if field_type_layout.element_type_layout().kind() == slang::TypeKind::Resource =>
+            {
+                let element_count = field_type_layout.element_count().unwrap();
+                if element_count != 0 && element_count != usize::MAX {
+                    anyhow::bail!(
+                        "only unbounded resource arrays are supported, for bindless textures: {field_name}"
+                    );
+                }
+
+                let element_type_layout = field_type_layout.element_type_layout();
+                let shape_with_flags = element_type_layout.resource_shape().unwrap();
+                if shape_with_flags as u32 & slang::ResourceShape::SlangTextureArrayFlag as u32 != 0
+                    || slang_base_shape(shape_with_flags) != slang::ResourceShape::SlangTexture2d
+                {
+                    anyhow::bail!("only Texture2D bindless arrays are supported: {field_name}");
+                }
+
+                let combined_sampler = shape_with_flags as u32
+                    & slang::ResourceShape::SlangTextureCombinedFlag as u32
+                    != 0;
+
+                StructField::BindlessTextures(BindlessTexturesStructField {
+                    field_name,
+                    binding: binding.expect("bindless textures field without binding"),
+                    combined_sampler,
+                    user_attributes,
+                })
+            }
+
+            slang::TypeKind::Array => {
+                let element_count = field_type_layout.element_count().unwrap();
+                if element_count == 0 || element_count == usize::MAX {
+                    anyhow::bail!("unsized array fields are not supported: {field_name}");
+                }
+
+                let element_stride =
+                    field_type_layout.element_stride(slang::ParameterCategory::Uniform);
+                let element_type_layout = field_type_layout.element_type_layout();
+
+                let element_type = match element_type_layout.kind() {
+                    slang::TypeKind::Scalar => {
+                        let scalar_type =
+                            scalar_from_slang(element_type_layout.scalar_type().unwrap());
+
+                        ArrayElementType::Scalar(ScalarVectorElementType { scalar_type })
+                    }
+
+                    slang::TypeKind::Vector => {
+                        let vec_element_type_layout = element_type_layout.element_type_layout();
+                        let scalar_type =
+                            scalar_from_slang(vec_element_type_layout.scalar_type().unwrap());
+
+                        ArrayElementType::Vector(VectorResultType {
+                            element_count: element_type_layout.element_count().unwrap(),
+                            element_type: VectorElementType::Scalar(ScalarVectorElementType {
+                                scalar_type,
+                            }),
+                        })
+                    }
+
+                    slang::TypeKind::Matrix => {
+                        let mat_element_type_layout = element_type_layout.element_type_layout();
+                        let scalar_type =
+                            scalar_from_slang(mat_element_type_layout.scalar_type().unwrap());
+
+                        ArrayElementType::Matrix(MatrixArrayElementType {
+                            row_count: element_type_layout.row_count().unwrap(),
+                            column_count: element_type_layout.column_count().unwrap(),
+                            element_type: VectorElementType::Scalar(ScalarVectorElementType {
+                                scalar_type,
+                            }),
+                            layout: matrix_layout_from_slang(element_type_layout),
+                            size: element_type_layout.size(slang::ParameterCategory::Uniform),
+                        })
+                    }
+
+                    slang::TypeKind::Struct => {
+                        let element_fields = reflect_struct_fields(element_type_layout)?;
+                        let element_type_name = element_type_layout.name().unwrap().to_string();
+
+                        ArrayElementType::Struct(StructFieldType {
+                            type_name: element_type_name,
+                            fields: element_fields,
+                            size: element_type_layout.size(slang::ParameterCategory::Uniform),
+                            alignment: element_type_layout
+                                .alignment(slang::ParameterCategory::Uniform)
+                                as usize,
+                        })
+                    }
+
+                    k => todo!("array element kind not handled: {k:?}"),
+                };
+
+                StructField::Array(ArrayStructField {
+                    field_name,
+                    binding: binding.expect("array field without binding"),
+                    element_count,
+                    element_stride,
+                    element_type,
+                    user_attributes,
                 })
             }
 
@@ -243,7 +432,11 @@ fn reflect_struct_fields(
                     struct_type: StructFieldType {
                         type_name: field_type_name,
                         fields: field_fields,
+                        size: field_type_layout.size(slang::ParameterCategory::Uniform),
+                        alignment: field_type_layout.alignment(slang::ParameterCategory::Uniform)
+                            as usize,
                     },
+                    user_attributes,
                 })
             }
 
@@ -251,13 +444,46 @@ fn reflect_struct_fields(
                 let shape_with_flags = field_type_layout.resource_shape().unwrap();
                 let slang_base_shape = slang_base_shape(shape_with_flags);
 
-                let resource_shape = match slang_base_shape {
-                    slang::ResourceShape::SlangTexture2d => ResourceShape::Texture2D,
-                    s => todo!("unhandled slang base shape: {s:?}"),
+                let is_array = shape_with_flags as u32
+                    & slang::ResourceShape::SlangTextureArrayFlag as u32
+                    != 0;
+
+                let resource_shape = match (slang_base_shape, is_array) {
+                    (slang::ResourceShape::SlangTexture1d, false) => ResourceShape::Texture1D,
+                    (slang::ResourceShape::SlangTexture2d, false) => ResourceShape::Texture2D,
+                    (slang::ResourceShape::SlangTexture2d, true) => ResourceShape::Texture2DArray,
+                    (slang::ResourceShape::SlangTexture3d, false) => ResourceShape::Texture3D,
+                    (slang::ResourceShape::SlangTextureCube, false) => ResourceShape::TextureCube,
+                    (slang::ResourceShape::SlangStructuredBuffer, _) => {
+                        match field_type_layout.resource_access() {
+                            Some(slang::ResourceAccess::ReadWrite) => {
+                                ResourceShape::MutableStructuredBuffer
+                            }
+                            _ => ResourceShape::StructuredBuffer,
+                        }
+                    }
+                    (s, a) => todo!("unhandled slang base shape: {s:?}, array: {a}"),
                 };
 
                 let result_type = field_type_layout.resource_result_type().unwrap();
                 let result_type = match result_type.kind() {
+                    // the element type layout has the buffer's (std430) field offsets,
+                    // which the result type alone doesn't
+                    slang::TypeKind::Struct => {
+                        let element_type_layout = field_type_layout.element_type_layout();
+                        let element_fields = reflect_struct_fields(element_type_layout)?;
+                        let element_type_name = element_type_layout.name().unwrap().to_string();
+
+                        ResourceResultType::Struct(StructFieldType {
+                            type_name: element_type_name,
+                            fields: element_fields,
+                            size: element_type_layout.size(slang::ParameterCategory::Uniform),
+                            alignment: element_type_layout
+                                .alignment(slang::ParameterCategory::Uniform)
+                                as usize,
+                        })
+                    }
+
                     slang::TypeKind::Vector => {
                         let element_count = result_type.element_count();
 
@@ -270,14 +496,114 @@ fn reflect_struct_fields(
                             element_type,
                         })
                     }
+
+                    slang::TypeKind::Scalar => {
+                        let scalar_type = scalar_from_slang(result_type.scalar_type());
+
+                        ResourceResultType::Scalar(ScalarVectorElementType { scalar_type })
+                    }
+
                     k => todo!("result type kind not handled: {k:?}"),
                 };
 
+                // the std430 (or scalar) stride, which can be larger than the element's size
+                let element_stride = matches!(
+                    resource_shape,
+                    ResourceShape::StructuredBuffer | ResourceShape::MutableStructuredBuffer
+                )
+                .then(|| {
+                    field_type_layout
+                        .element_type_layout()
+                        .stride(slang::ParameterCategory::Uniform)
+                });
+
+                let combined_sampler = shape_with_flags as u32
+                    & slang::ResourceShape::SlangTextureCombinedFlag as u32
+                    != 0;
+
                 StructField::Resource(ResourceStructField {
                     field_name,
                     binding: binding.expect("resource struct field without binding"),
                     resource_shape,
                     result_type,
+                    element_stride,
+                    combined_sampler,
+                    user_attributes,
+                })
+            }
+
+            slang::TypeKind::SamplerState => StructField::Sampler(SamplerStructField {
+                field_name,
+                binding: binding.expect("sampler struct field without binding"),
+                user_attributes,
+            }),
+
+            // this gets its own descriptor set, rather than a binding in the parent's
+            slang::TypeKind::ParameterBlock => {
+                let element_type = reflect_parameter_block_element(field_type_layout)?;
+
+                StructField::ParameterBlock(ParameterBlockStructField {
+                    field_name,
+                    element_type,
+                    user_attributes,
+                })
+            }
+
+            slang::TypeKind::Pointer => {
+                let value_type_layout = field_type_layout.element_type_layout();
+
+                let value_type = match value_type_layout.kind() {
+                    slang::TypeKind::Scalar => {
+                        let scalar_type =
+                            scalar_from_slang(value_type_layout.scalar_type().unwrap());
+
+                        PointerValueType::Scalar(ScalarVectorElementType { scalar_type })
+                    }
+
+                    slang::TypeKind::Vector => {
+                        let vec_element_type_layout = value_type_layout.element_type_layout();
+                        let scalar_type =
+                            scalar_from_slang(vec_element_type_layout.scalar_type().unwrap());
+
+                        PointerValueType::Vector(VectorResultType {
+                            element_count: value_type_layout.element_count().unwrap(),
+                            element_type: VectorElementType::Scalar(ScalarVectorElementType {
+                                scalar_type,
+                            }),
+                        })
+                    }
+
+                    // avoid recursing forever on ie a linked list node
+                    slang::TypeKind::Struct
+                        if value_type_layout.name() == struct_type_layout.name() =>
+                    {
+                        let type_name = value_type_layout.name().unwrap().to_string();
+
+                        PointerValueType::SelfStruct(SelfStructValueType { type_name })
+                    }
+
+                    slang::TypeKind::Struct => {
+                        let value_fields = reflect_struct_fields(value_type_layout)?;
+                        let value_type_name = value_type_layout.name().unwrap().to_string();
+
+                        PointerValueType::Struct(StructFieldType {
+                            type_name: value_type_name,
+                            fields: value_fields,
+                            size: value_type_layout.size(slang::ParameterCategory::Uniform),
+                            alignment: value_type_layout
+                                .alignment(slang::ParameterCategory::Uniform)
+                                as usize,
+                        })
+                    }
+
+                    k => todo!("pointer value kind not handled: {k:?}"),
+                };
+
+                StructField::Pointer(PointerStructField {
+                    field_name,
+                    binding: binding.expect("pointer field without binding"),
+                    value_type,
+                    user_attributes,
                 })
             }
 
@@ -290,6 +616,38 @@ fn reflect_struct_fields(
     Ok(fields)
 }
 
+/// attributes declared in shaders/source/attributes.slang, ie '[RustName("albedo")]'
+fn reflect_user_attributes(param: &slang::reflection::VariableLayout) -> Vec<UserAttribute> {
+    let Some(variable) = param.variable() else {
+        return vec![];
+    };
+
+    variable
+        .user_attributes()
+        .map(|attribute| {
+            // slang may include the declaring struct's suffix, ie 'RustNameAttribute'
+            let name = attribute.name();
+            let name = name.strip_suffix("Attribute").unwrap_or(name).to_string();
+
+            let arguments = (0..attribute.argument_count())
+                .map(|index| {
+                    if let Some(value) = attribute.argument_value_string(index) {
+                        UserAttributeArgument::String(value.to_string())
+                    } else if let Some(value) = attribute.argument_value_int(index) {
+                        UserAttributeArgument::Int(value)
+                    } else if let Some(value) = attribute.argument_value_float(index) {
+                        UserAttributeArgument::Float(value)
+                    } else {
+                        todo!("user attribute argument type not handled: {name}")
+                    }
+                })
+                .collect();
+
+            UserAttribute { name, arguments }
+        })
+        .collect()
+}
+
 fn slang_base_shape(shape_with_flags: slang::ResourceShape) -> slang::ResourceShape {
     // this is reproducing the way the base shape mask is used here:
     // https://github.com/shader-slang/slang/blob/9f9d28c1f496132dc71b80252b0eeddfa28cc8bc/source/slang/slang-reflection-json.cpp#L470
@@ -298,10 +656,24 @@ fn slang_base_shape(shape_with_flags: slang::ResourceShape) -> slang::ResourceSh
     unsafe { std::mem::transmute(base_shape) }
 }
 
+fn matrix_layout_from_slang(type_layout: &slang::reflection::TypeLayout) -> MatrixLayout {
+    match type_layout.matrix_layout_mode() {
+        slang::MatrixLayoutMode::RowMajor => MatrixLayout::RowMajor,
+        slang::MatrixLayoutMode::ColumnMajor => MatrixLayout::ColumnMajor,
+        m => todo!("matrix layout mode not handled: {m:?}"),
+    }
+}
+
 fn scalar_from_slang(scalar: slang::ScalarType) -> ScalarType {
     match scalar {
+        slang::ScalarType::Bool => ScalarType::Bool,
+        slang::ScalarType::Int32 => ScalarType::Int32,
         slang::ScalarType::Uint32 => ScalarType::Uint32,
+        slang::ScalarType::Int64 => ScalarType::Int64,
+        slang::ScalarType::Uint64 => ScalarType::Uint64,
+        slang::ScalarType::Float16 => ScalarType::Float16,
         slang::ScalarType::Float32 => ScalarType::Float32,
+        slang::ScalarType::Float64 => ScalarType::Float64,
         k => todo!("slang scalar type not handled: {k:?}"),
     }
 }
@@ -338,6 +710,10 @@ fn param_binding(param: &slang::reflection::VariableLayout) -> Option<Binding> {
 
         slang::ParameterCategory::None => None,
 
+        // nested ParameterBlocks, which get their own descriptor set
+        slang::ParameterCategory::RegisterSpace
+        | slang::ParameterCategory::SubElementRegisterSpace => None,
+
         c => todo!("param category not handled: {c:?}"),
     }
 }
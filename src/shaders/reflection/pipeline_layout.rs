@@ -194,6 +194,7 @@ impl DescriptorSetLayoutBuilder {
             binding: vk_binding_index,
             descriptor_type: ReflectedBindingType::ConstantBuffer,
             descriptor_count: 1,
+            unbounded: false,
             stage_flags: pipeline_layout_builder.current_stage_flags,
             size,
         };
@@ -231,13 +232,20 @@ impl DescriptorSetLayoutBuilder {
     ) {
         let binding_type =
             type_layout.descriptor_set_descriptor_range_type(relative_set_index, range_index);
-        if binding_type == slang::BindingType::PushConstant {
-            // this is accounted for in add_sub_object_range
+        if matches!(
+            binding_type,
+            slang::BindingType::PushConstant | slang::BindingType::ParameterBlock
+        ) {
+            // these are accounted for in add_sub_object_range;
+            // a nested ParameterBlock is its own descriptor set,
+            // and shouldn't shift the binding numbers of the remaining fields
             return;
         }
 
         let descriptor_count = type_layout
             .descriptor_set_descriptor_range_descriptor_count(relative_set_index, range_index);
+        // SLANG_UNBOUNDED_SIZE, as a signed count
+        let unbounded = descriptor_count == -1;
 
         // this relies on using no manual binding annotations
         let vk_binding_index = self.binding_ranges.len() as u32;
@@ -247,7 +255,12 @@ impl DescriptorSetLayoutBuilder {
         let descriptor_set_layout_binding = ReflectedDescriptorSetLayoutBinding {
             binding: vk_binding_index,
             descriptor_type,
-            descriptor_count: descriptor_count as u32,
+            descriptor_count: if unbounded {
+                0
+            } else {
+                descriptor_count as u32
+            },
+            unbounded,
             stage_flags: pipeline_layout_builder.current_stage_flags,
             size,
         };
@@ -320,6 +333,11 @@ impl ReflectedBindingType {
             slang::BindingType::Texture => Self::Texture,
             slang::BindingType::ConstantBuffer => Self::ConstantBuffer,
             slang::BindingType::CombinedTextureSampler => Self::CombinedTextureSampler,
+            // StructuredBuffer and ByteAddressBuffer, and their RW versions
+            slang::BindingType::RawBuffer => Self::StorageBuffer,
+            slang::BindingType::MutableRawBuffer => Self::StorageBuffer,
+            // RWBuffer
+            slang::BindingType::MutableTypedBuffer => Self::StorageTexelBuffer,
 
             slang::BindingType::PushConstant => todo!(),
             slang::BindingType::ParameterBlock => todo!(),
@@ -327,15 +345,12 @@ impl ReflectedBindingType {
             slang::BindingType::VaryingInput => todo!(),
             slang::BindingType::VaryingOutput => todo!(),
             slang::BindingType::TypedBuffer => todo!(),
-            slang::BindingType::RawBuffer => todo!(),
             slang::BindingType::InputRenderTarget => todo!(),
             slang::BindingType::InlineUniformData => todo!(),
             slang::BindingType::RayTracingAccelerationStructure => todo!(),
             slang::BindingType::ExistentialValue => todo!(),
             slang::BindingType::MutableFlag => todo!(),
             slang::BindingType::MutableTeture => todo!(),
-            slang::BindingType::MutableTypedBuffer => todo!(),
-            slang::BindingType::MutableRawBuffer => todo!(),
             slang::BindingType::BaseMask => todo!(),
             slang::BindingType::ExtMask => todo!(),
             slang::BindingType::Unknown => todo!(),